@@ -38,15 +38,3 @@ int helloc_init(struct event_base *base)
   return 0;
 }
 
-int helloc_destroy(struct event_base* base)
-{
-  if (base == NULL)
-  {
-    return -1;
-  }
-
-  event_base_free(base);
-
-  return 0;
-}
-
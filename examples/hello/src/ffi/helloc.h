@@ -4,6 +4,5 @@
 #include <event2/event.h>
 
 int helloc_init(struct event_base* base);
-int helloc_destroy(struct event_base* base);
 
 #endif //HELLOC_H
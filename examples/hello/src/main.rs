@@ -87,9 +87,8 @@ fn main() {
         base.run();
     }
 
-    // TODO: expose base_free from libevent-rs
-    let ret = unsafe { ffi::helloc_destroy(base.as_raw().as_ptr()) };
-    assert_eq!(ret, 0);
+    // Frees the underlying `event_base`.
+    drop(base);
 
     println!("Exiting");
 }
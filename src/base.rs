@@ -1,13 +1,20 @@
 #![allow(dead_code)]
 
 use bitflags::bitflags;
-use std::io;
-use std::os::raw::{c_int, c_short, c_void};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::io::{self, Write};
+use std::os::raw::{c_char, c_int, c_short, c_void};
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 use std::time::Duration;
 
 use super::event::*;
+use crate::debug::EventInfo;
 use crate::EventCallbackWrapper;
+use crate::{BackendFeatures, BaseConfigFlags, EventConfig};
 
 /// A file descriptor in libevent.
 pub type EvutilSocket = c_int;
@@ -22,27 +29,214 @@ pub type EventCallbackCtx = *mut c_void;
 pub type EventCallbackFlags = c_short;
 
 /// Convenience function for mapping Rust's `Duration` to libevent's `timeval`.
-fn to_timeval(duration: Duration) -> libevent_sys::timeval {
+pub(crate) fn to_timeval(duration: Duration) -> libevent_sys::timeval {
     libevent_sys::timeval {
         tv_sec: duration.as_secs() as _,
         tv_usec: duration.subsec_micros() as _,
     }
 }
 
+/// Convenience function for mapping libevent's `timeval` to Rust's `Duration`,
+/// where negative values map to zero.
+pub(crate) fn from_timeval(tv: libevent_sys::timeval) -> Duration {
+    if tv.tv_sec < 0 || tv.tv_usec < 0 {
+        return Duration::from_secs(0);
+    }
+
+    Duration::from_secs(tv.tv_sec as u64) + Duration::from_micros(tv.tv_usec as u64)
+}
+
 /// Wrapper for libevent's `event_base` which is responsible for executing
 /// associated events.
+///
+/// A `Base` created via `new`, `with_config` or `from_raw_owned` owns its
+/// `event_base` and frees it when dropped, while one created via `from_raw`
+/// leaves that to the caller. Events spawned on the base which
+/// are still alive at that point are detached: the closures of those spawned
+/// via `spawn` are dropped right away, while those of the others are dropped
+/// along with their handles, and any further use of those handles fails.
 pub struct Base {
     base: NonNull<libevent_sys::event_base>,
+    owned: bool,
+    state: Arc<BaseState>,
+}
+
+/// Whether any `Base` has been created (or borrowed) within the process.
+static BASE_CREATED: AtomicBool = AtomicBool::new(false);
+
+/// Whether any `Base` has been created (or borrowed) within the process, after
+/// which some global libevent settings can no longer be changed.
+pub(crate) fn any_base_created() -> bool {
+    BASE_CREATED.load(Ordering::Acquire)
+}
+
+/// Whether libevent's thread support is initialized, so that bases created
+/// from now on lock themselves.
+fn thread_support_initialized() -> bool {
+    #[cfg(feature = "threading")]
+    {
+        crate::thread::is_initialized()
+    }
+    #[cfg(not(feature = "threading"))]
+    {
+        false
+    }
+}
+
+/// Initializes libevent's thread support ahead of creating a new base, if
+/// the `threading` feature is enabled.
+fn init_thread_support() {
+    #[cfg(feature = "threading")]
+    {
+        // This only fails if a base was already created another way (e.g. via
+        // `from_raw`), in which case new bases are merely limited to a single
+        // thread, just as without the feature.
+        let _ = crate::thread::init();
+    }
+}
+
+/// State shared between a `Base` and the events spawned on it.
+#[derive(Debug)]
+pub(crate) struct BaseState {
+    /// Cleared once the underlying `event_base` has been freed. Held for
+    /// reading while handles hand the base's events to libevent, so that the
+    /// base is not freed meanwhile.
+    alive: RwLock<bool>,
+    /// Whether the base locks itself around libevent operations.
+    locking: bool,
+    /// Running count of callbacks dispatched to events spawned on the base.
+    dispatched: AtomicUsize,
+    /// Closures scheduled via `Base::once_*` which have not run yet, keyed by
+    /// their context pointer, along with the function freeing each of them.
+    pending_once: Mutex<HashMap<usize, unsafe fn(EventCallbackCtx)>>,
+    /// Events spawned via `Base::spawn` which have not finished yet, and which
+    /// no handle but their closure's own can free, keyed by their raw `event`,
+    /// along with the function freeing each of them.
+    spawned: Mutex<HashMap<usize, FreeSpawned>>,
+}
+
+/// Frees an event spawned via `Base::spawn`, along with its closure.
+type FreeSpawned = unsafe fn(NonNull<libevent_sys::event>);
+
+impl BaseState {
+    fn new(locking: bool) -> Arc<Self> {
+        Arc::new(BaseState {
+            alive: RwLock::new(true),
+            locking,
+            dispatched: AtomicUsize::new(0),
+            pending_once: Mutex::new(HashMap::new()),
+            spawned: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Whether the underlying `event_base` has not been freed yet.
+    pub(crate) fn is_alive(&self) -> bool {
+        *self.alive.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Runs `f` unless the underlying `event_base` has been freed, keeping it
+    /// from being freed until `f` returns, even if the `Base` is dropped on
+    /// another thread meanwhile.
+    ///
+    /// `f` must not call back into this (e.g. via `is_alive`), as a pending
+    /// `set_freed` may keep the lock from being taken again.
+    pub(crate) fn while_alive<O>(&self, f: impl FnOnce() -> O) -> Option<O> {
+        let alive = self.alive.read().unwrap_or_else(|e| e.into_inner());
+        if *alive {
+            Some(f())
+        } else {
+            None
+        }
+    }
+
+    /// Marks the underlying `event_base` as freed, once no handle is within
+    /// `while_alive` anymore. Must be called right before freeing it.
+    ///
+    /// The lock is not held while the base is then freed, as the finalizers
+    /// libevent runs may drop handles, which check `is_alive` in turn.
+    fn set_freed(&self) {
+        *self.alive.write().unwrap_or_else(|e| e.into_inner()) = false;
+    }
+
+    /// Records that a callback is being dispatched.
+    pub(crate) fn note_dispatch(&self) {
+        self.dispatched.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a closure handed to `event_base_once`, to be freed via `free`
+    /// unless it runs before the base is freed.
+    fn track_once(&self, ctx: EventCallbackCtx, free: unsafe fn(EventCallbackCtx)) {
+        self.pending_once
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(ctx as usize, free);
+    }
+
+    /// Forgets a closure handed to `event_base_once`, as it is being run.
+    fn untrack_once(&self, ctx: EventCallbackCtx) {
+        self.pending_once
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&(ctx as usize));
+    }
+
+    /// Records an event spawned via `Base::spawn`, to be freed via `free`
+    /// unless it finishes before the base is freed.
+    fn track_spawned(&self, event: NonNull<libevent_sys::event>, free: FreeSpawned) {
+        self.spawned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(event.as_ptr() as usize, free);
+    }
+
+    /// Forgets an event spawned via `Base::spawn`, as it is being freed.
+    pub(crate) fn untrack_spawned(&self, event: NonNull<libevent_sys::event>) {
+        self.spawned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&(event.as_ptr() as usize));
+    }
+
+    /// Frees all events spawned via `Base::spawn` which have not finished yet,
+    /// along with their closures.
+    ///
+    /// Must only be called right before freeing the underlying `event_base`,
+    /// while no callback is running.
+    fn free_spawned(&self) {
+        let spawned = std::mem::take(&mut *self.spawned.lock().unwrap_or_else(|e| e.into_inner()));
+
+        for (event, free) in spawned {
+            let event = NonNull::new(event as *mut libevent_sys::event);
+            if let Some(event) = event {
+                unsafe { free(event) };
+            }
+        }
+    }
+
+    /// Frees all closures handed to `event_base_once` which have not run yet.
+    ///
+    /// Must only be called right before freeing the underlying `event_base`,
+    /// after which libevent never runs them.
+    fn free_pending_once(&self) {
+        let pending =
+            std::mem::take(&mut *self.pending_once.lock().unwrap_or_else(|e| e.into_inner()));
+
+        for (ctx, free) in pending {
+            unsafe { free(ctx as EventCallbackCtx) };
+        }
+    }
 }
 
 /// The handle that abstracts over libevent's API in Rust.
 impl Base {
     /// Creates a new instance of `Base`.
     pub fn new() -> Result<Self, io::Error> {
+        init_thread_support();
+
         let base = unsafe { libevent_sys::event_base_new() };
 
         if let Some(base) = NonNull::new(base) {
-            Ok(unsafe { Self::from_raw(base) })
+            Ok(unsafe { Self::from_raw_parts(base, true, thread_support_initialized()) })
         } else {
             Err(io::Error::new(
                 io::ErrorKind::Other,
@@ -51,16 +245,122 @@ impl Base {
         }
     }
 
+    /// Creates a new instance of `Base` whose backend is chosen according to
+    /// the given `EventConfig`.
+    ///
+    /// Fails if no available backend satisfies the config's requirements
+    /// (e.g. every backend providing the required features was avoided).
+    pub fn with_config(config: &EventConfig) -> io::Result<Self> {
+        init_thread_support();
+
+        let base = unsafe { libevent_sys::event_base_new_with_config(config.as_raw().as_ptr()) };
+
+        if let Some(base) = NonNull::new(base) {
+            let locking =
+                thread_support_initialized() && !config.flags().contains(BaseConfigFlags::NOLOCK);
+            Ok(unsafe { Self::from_raw_parts(base, true, locking) })
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "No libevent backend satisfies the given config",
+            ))
+        }
+    }
+
     /// Creates a new instance of `Base` using a raw, non-null `event_base`
-    /// pointer.
+    /// pointer which remains owned by the caller, and is never freed by the
+    /// returned `Base`.
     ///
     /// # Safety
     ///
     /// This function expects a non-null pointer, and thus does no such checks
     /// internally. Thus the caller is responsible for checking the
-    /// `event_base` validity.
+    /// `event_base` validity, and for keeping it alive for as long as the
+    /// returned `Base`, and any events spawned on it, are in use.
+    ///
+    /// The `event_base` may have been created before thread support was
+    /// initialized, or with `BaseConfigFlags::NOLOCK`, so the returned `Base`
+    /// is never taken to lock itself, and refuses `spawn_shared`.
     pub unsafe fn from_raw(base: NonNull<libevent_sys::event_base>) -> Self {
-        Base { base }
+        Self::from_raw_parts(base, false, false)
+    }
+
+    /// Creates a new instance of `Base` using a raw, non-null `event_base`
+    /// pointer, taking ownership of it.
+    ///
+    /// # Safety
+    ///
+    /// Same as for `from_raw`, except that the `event_base` is freed when the
+    /// returned `Base` is dropped, so it must not be freed elsewhere.
+    pub unsafe fn from_raw_owned(base: NonNull<libevent_sys::event_base>) -> Self {
+        Self::from_raw_parts(base, true, false)
+    }
+
+    /// Helper for the constructors, which marks that a base was created.
+    unsafe fn from_raw_parts(
+        base: NonNull<libevent_sys::event_base>,
+        owned: bool,
+        locking: bool,
+    ) -> Self {
+        BASE_CREATED.store(true, Ordering::Release);
+
+        Base {
+            base,
+            owned,
+            state: BaseState::new(locking),
+        }
+    }
+
+    /// Creates a second, non-owning `Base` for the `event_base` of an existing
+    /// one, which shares its state, so that events spawned through either are
+    /// treated alike.
+    ///
+    /// # Safety
+    ///
+    /// The `Base` owning `state` must outlive the returned `Base`.
+    pub(crate) unsafe fn view(
+        base: NonNull<libevent_sys::event_base>,
+        state: Arc<BaseState>,
+    ) -> Self {
+        Base {
+            base,
+            owned: false,
+            state,
+        }
+    }
+
+    /// Whether this base locks itself around libevent operations, which is
+    /// the case if thread support was initialized before it was created, and
+    /// it was not configured with `BaseConfigFlags::NOLOCK`. Bases wrapped
+    /// via `from_raw` or `from_raw_owned` are never taken to lock themselves.
+    pub fn is_locking(&self) -> bool {
+        self.state.locking
+    }
+
+    /// Whether this `Base` was created via `from_raw`, and thus does not free
+    /// the underlying `event_base`.
+    pub fn is_borrowed(&self) -> bool {
+        !self.owned
+    }
+
+    /// Frees the base via libevent's `event_base_free_nofinalize`, which unlike
+    /// dropping the `Base` does not run finalizers of pending events, so the
+    /// closures of events dropped since the loop last ran are leaked.
+    ///
+    /// For a borrowed base this only releases the handle.
+    pub fn free_nofinalize(mut self) {
+        if self.owned {
+            self.owned = false;
+            self.state.free_pending_once();
+            self.state.free_spawned();
+            self.state.set_freed();
+            unsafe { libevent_sys::event_base_free_nofinalize(self.base.as_ptr()) };
+        }
+    }
+
+    /// The state shared with events spawned on this base.
+    pub(crate) fn state(&self) -> Arc<BaseState> {
+        self.state.clone()
     }
 
     /// Exposes the raw, non-null `event_base` pointer.
@@ -74,6 +374,153 @@ impl Base {
         self.base
     }
 
+    /// Wrapper for libevent's `event_base_get_method`, which names the
+    /// backend (e.g. `"epoll"`) that this base is using.
+    pub fn method(&self) -> &str {
+        let method =
+            unsafe { CStr::from_ptr(libevent_sys::event_base_get_method(self.base.as_ptr())) };
+        method.to_str().unwrap_or("")
+    }
+
+    /// Wrapper for libevent's `event_base_get_features`, which describes
+    /// what the backend in use supports.
+    pub fn features(&self) -> BackendFeatures {
+        let features = unsafe { libevent_sys::event_base_get_features(self.base.as_ptr()) };
+        BackendFeatures::from_bits_truncate(features as u32)
+    }
+
+    /// Wrapper for libevent's `event_base_get_num_events`, which returns the
+    /// number of events currently in any of the states given by `flags`.
+    pub fn num_events(&self, flags: EventCountFlags) -> usize {
+        let count =
+            unsafe { libevent_sys::event_base_get_num_events(self.base.as_ptr(), flags.bits()) };
+        count.max(0) as usize
+    }
+
+    /// Wrapper for libevent's `event_base_dump_events`, which writes a
+    /// human-readable list of the events registered with the base, and of
+    /// those currently active, to `out`.
+    ///
+    /// libevent writes to a C `FILE`, so the list is collected in memory via
+    /// `open_memstream` first.
+    pub fn dump_events(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut buf: *mut c_char = std::ptr::null_mut();
+        let mut len: libc::size_t = 0;
+
+        let file = unsafe { libc::open_memstream(&mut buf, &mut len) };
+        if file.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+
+        unsafe { libevent_sys::event_base_dump_events(self.base.as_ptr(), file.cast()) };
+
+        // Only once closed are `buf` and `len` up to date.
+        let result = if unsafe { libc::fclose(file) } != 0 {
+            Err(io::Error::last_os_error())
+        } else if buf.is_null() {
+            Ok(())
+        } else {
+            let dump = unsafe { std::slice::from_raw_parts(buf as *const u8, len) };
+            out.write_all(dump)
+        };
+
+        unsafe { libc::free(buf.cast()) };
+        result
+    }
+
+    /// Wrapper for libevent's `event_base_foreach_event`, which calls `f` with
+    /// the details of each event registered with the base, or currently
+    /// active. This may include libevent's own event for waking up the loop
+    /// from other threads.
+    ///
+    /// The base is locked for the duration, so `f` must neither change any
+    /// events of the base (e.g. via their handles), nor call into the base.
+    pub fn for_each_event<F: FnMut(EventInfo)>(&self, f: F) -> io::Result<()> {
+        let mut ctx = (f, EventInfo::monotonic_now());
+
+        let ret = unsafe {
+            libevent_sys::event_base_foreach_event(
+                self.base.as_ptr(),
+                Some(handle_foreach_event::<F>),
+                &mut ctx as *mut (F, Duration) as *mut c_void,
+            )
+        };
+
+        if ret != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to iterate over events",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Wrapper for libevent's `event_base_get_max_events`, which returns the
+    /// largest number of events that have been in any of the states given by
+    /// `flags` at once. If `clear` is set, the maximum is reset afterwards.
+    pub fn max_events(&self, flags: EventCountFlags, clear: bool) -> usize {
+        let count = unsafe {
+            libevent_sys::event_base_get_max_events(
+                self.base.as_ptr(),
+                flags.bits(),
+                clear as c_int,
+            )
+        };
+        count.max(0) as usize
+    }
+
+    /// Wrapper for libevent's `event_base_priority_init`, which sets the
+    /// number of priorities events on this base can have.
+    ///
+    /// Must be called before any events are active; it is best called right
+    /// after creating the base.
+    pub fn init_priorities(&mut self, npriorities: u32) -> io::Result<()> {
+        let npriorities = npriorities.min(c_int::MAX as u32) as c_int;
+
+        if unsafe { libevent_sys::event_base_priority_init(self.base.as_ptr(), npriorities) } != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to initialize priorities",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Wrapper for libevent's `event_base_get_npriorities`, which returns the
+    /// number of priorities events on this base can have.
+    pub fn num_priorities(&self) -> u32 {
+        let npriorities = unsafe { libevent_sys::event_base_get_npriorities(self.base.as_ptr()) };
+        npriorities.max(0) as u32
+    }
+
+    /// Wrapper for libevent's `event_base_init_common_timeout`, which sets up
+    /// a queue for events sharing the given `duration`.
+    ///
+    /// Spawning events with the returned `CommonTimeout` on this base makes
+    /// adding and removing their timeouts O(1), rather than O(log n) for the
+    /// heap used otherwise, which pays off for large numbers of timers sharing
+    /// a few durations. libevent supports a limited number of these per base.
+    pub fn common_timeout(&self, duration: Duration) -> io::Result<CommonTimeout> {
+        let tv = to_timeval(duration);
+        let common =
+            unsafe { libevent_sys::event_base_init_common_timeout(self.base.as_ptr(), &tv) };
+
+        if common.is_null() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to initialize common timeout",
+            ));
+        }
+
+        Ok(CommonTimeout {
+            tv: unsafe { *common },
+            duration,
+            base: Arc::downgrade(&self.state),
+        })
+    }
+
     /// Wrapper for libevent's `event_base_loop`, which runs the event loop in
     /// a manner defined by the `LoopFlags` input.
     pub fn loop_(&self, flags: LoopFlags) -> ExitReason {
@@ -91,8 +538,9 @@ impl Base {
                         ExitReason::GotExit
                     } else if libevent_sys::event_base_got_break(self.base.as_ptr()) != 0i32 {
                         ExitReason::GotBreak
+                    } else if flags.intersects(LoopFlags::ONCE | LoopFlags::NONBLOCK) {
+                        ExitReason::Completed
                     } else {
-                        // TODO: This should match flags for `EVLOOP_ONCE`, `_NONBLOCK`, etc.
                         ExitReason::Unknown { flags, exit_code }
                     }
                 }
@@ -103,6 +551,19 @@ impl Base {
         }
     }
 
+    /// Runs `loop_`, additionally counting the callbacks dispatched while it
+    /// was running.
+    pub(crate) fn loop_counted(&self, flags: LoopFlags) -> LoopOutcome {
+        let before = self.state.dispatched.load(Ordering::Relaxed);
+        let reason = self.loop_(flags);
+        let after = self.state.dispatched.load(Ordering::Relaxed);
+
+        LoopOutcome {
+            reason,
+            dispatched: after.wrapping_sub(before),
+        }
+    }
+
     /// Wrapper for libevent's `event_base_loopexit`, which tells the running
     /// event loop to exit after a specified `Duration`.
     pub fn loopexit(&self, timeout: Duration) -> i32 {
@@ -134,13 +595,8 @@ impl Base {
         callback: EventCallbackFn,
         callback_ctx: Option<EventCallbackCtx>,
     ) -> Option<NonNull<libevent_sys::event>> {
-        let fd: EvutilSocket = if let Some(fd) = fd {
-            // Actual fd
-            fd
-        } else {
-            // Timer
-            -1
-        };
+        // A negative fd means a pure timer to libevent.
+        let fd: EvutilSocket = fd.unwrap_or(-1);
 
         let callback_ctx = if let Some(ctx) = callback_ctx {
             ctx
@@ -171,13 +627,8 @@ impl Base {
         callback: EventCallbackFn,
         callback_ctx: Option<EventCallbackCtx>,
     ) -> c_int {
-        let fd: EvutilSocket = if let Some(fd) = fd {
-            // Actual fd
-            fd
-        } else {
-            // Timer
-            -1
-        };
+        // A negative fd means a pure timer to libevent.
+        let fd: EvutilSocket = fd.unwrap_or(-1);
 
         let callback_ctx = if let Some(ctx) = callback_ctx {
             ctx
@@ -197,6 +648,13 @@ impl Base {
         }
     }
 
+    /// Wrapper for libevent's `event_priority_set`, which sets the priority of
+    /// an initialized, but not yet active, `event`.
+    pub fn event_priority_set(&self, event: NonNull<libevent_sys::event>, priority: u32) -> c_int {
+        let priority = priority.min(c_int::MAX as u32) as c_int;
+        unsafe { libevent_sys::event_priority_set(event.as_ptr(), priority) }
+    }
+
     /// Wrapper for libevent's `event_add`, which activates an initialized
     /// `event` for a pre-defined `Base` and a given timeout interval.
     pub fn event_add(
@@ -218,12 +676,24 @@ impl Base {
 
 unsafe impl Send for Base {}
 
+impl Drop for Base {
+    fn drop(&mut self) {
+        if self.owned {
+            self.state.free_pending_once();
+            self.state.free_spawned();
+            self.state.set_freed();
+            unsafe { libevent_sys::event_base_free(self.base.as_ptr()) };
+        }
+    }
+}
+
 impl<S, T: Exec<S, F>, F> EventCallbackWrapper<S, T, F> {
-    pub fn new(inner: F, event: Event<S>) -> Box<Self> {
+    pub fn new(inner: F, kind: T, event: Event<S>, base: Arc<BaseState>) -> Box<Self> {
         Box::new(Self {
-            inner,
+            inner: Some(inner),
             event: Some(event),
-            _phantom: std::marker::PhantomData::default(),
+            base,
+            kind,
         })
     }
 }
@@ -238,6 +708,11 @@ pub(crate) unsafe extern "C" fn finalize_wrapped_callback<S, T, F>(
     event: *mut libevent_sys::event,
     ctx: EventCallbackCtx,
 ) {
+    // Nothing to free if the context was never assigned.
+    if ctx.is_null() {
+        return;
+    }
+
     // Wrapper was allocated with Box, now free it with Drop.
     let cb: *mut EventCallbackWrapper<S, T, F> = ctx as *mut EventCallbackWrapper<S, T, F>;
     let owned_cb = Box::from_raw(cb);
@@ -255,6 +730,7 @@ pub(crate) extern "C" fn handle_wrapped_callback<S, T, F>(
     ctx: EventCallbackCtx,
 ) where
     T: Exec<S, F>,
+    Event<S>: CallbackHandle,
 {
     let cb_ref = unsafe {
         let cb: *mut EventCallbackWrapper<S, T, F> = ctx as *mut EventCallbackWrapper<S, T, F>;
@@ -265,26 +741,149 @@ pub(crate) extern "C" fn handle_wrapped_callback<S, T, F>(
     let flags = EventFlags::from_bits_truncate(event as u32);
     let ev = cb_ref.event.as_mut().expect("Missing event for callback");
 
+    cb_ref.base.note_dispatch();
+
     ev.set_in_callback(true);
-    <T as Exec<S, F>>::exec(ev, fd, flags, &mut cb_ref.inner);
+    let outer = begin_deferring();
+    let control = cb_ref.kind.exec(ev, fd, flags, &mut cb_ref.inner);
     ev.set_in_callback(false);
 
-    // row, row, row your boat..
-    if ev.stopped() {
+    // Events dropped from within the callback (possibly this one) are only
+    // finalized now that the closure is off the stack. libevent runs their
+    // finalizers later on, so the wrapper stays valid for the rest of this
+    // function.
+    finalize_deferred(outer);
+
+    // A closure which was taken to be run only once leaves the event finished,
+    // so free it right away rather than waiting on the handles.
+    if cb_ref.inner.is_none() {
+        let event = cb_ref.event.take().expect("Missing event for finish");
+        event.finish();
+        return;
+    }
+
+    // There is no one to report failures to here; they only occur for events
+    // which are gone already.
+    let _ = ev.apply(control);
+
+    // row, row, row your boat.. unless other handles remain, through which
+    // the event may be added back (e.g. via `restart`), and which free it
+    // once dropped.
+    if ev.stopped() && ev.is_sole_handle() {
         let event = cb_ref.event.take().expect("Missing event for drop");
-        drop(event)
+        event.finish();
+    }
+}
+
+thread_local! {
+    /// Events dropped on this thread from within their own callback, which are
+    /// finalized once the callback has returned. `None` unless a callback runs
+    /// on this thread.
+    static DEFERRED_FINALIZE: RefCell<Option<Vec<DeferredFinalize>>> = RefCell::new(None);
+}
+
+type DeferredFinalize = (
+    NonNull<libevent_sys::event>,
+    libevent_sys::event_finalize_callback_fn,
+);
+
+/// Hands over an event dropped from within its own callback, to be freed via
+/// `event_free_finalize` once the callback has returned.
+///
+/// Only the thread running the callback knows that it has yet to return (and
+/// thus to finalize the event), so this fails on any other thread, in which
+/// case the caller is left to finalize the event right away.
+pub(crate) fn defer_finalize(
+    event: NonNull<libevent_sys::event>,
+    finalizer: libevent_sys::event_finalize_callback_fn,
+) -> bool {
+    DEFERRED_FINALIZE.with(|deferred| match deferred.borrow_mut().as_mut() {
+        Some(deferred) => {
+            deferred.push((event, finalizer));
+            true
+        }
+        None => false,
+    })
+}
+
+/// Starts taking events handed over via `defer_finalize`, as a callback is
+/// about to run on this thread, returning those taken so far for the callback
+/// it is nested in.
+fn begin_deferring() -> Option<Vec<DeferredFinalize>> {
+    DEFERRED_FINALIZE.with(|deferred| deferred.borrow_mut().replace(Vec::new()))
+}
+
+/// Frees the events handed over via `defer_finalize` while the callback which
+/// just returned on this thread was running, and goes back to taking them for
+/// the callback it was nested in (e.g. one turning another base), if any.
+fn finalize_deferred(outer: Option<Vec<DeferredFinalize>>) {
+    let deferred =
+        DEFERRED_FINALIZE.with(|deferred| std::mem::replace(&mut *deferred.borrow_mut(), outer));
+
+    for (event, finalizer) in deferred.unwrap_or_default() {
+        unsafe { libevent_sys::event_free_finalize(0, event.as_ptr(), finalizer) };
     }
 }
 
+/// A closure scheduled via `Base::once_timeout` or `Base::once_fd`, which is
+/// handed to libevent as the context of `event_base_once`.
+struct OnceCallback<F> {
+    inner: F,
+    base: Arc<BaseState>,
+}
+
+/// Acts as a C-compatible trampoline for closures scheduled via
+/// `event_base_once`, which libevent calls at most once.
+extern "C" fn handle_once_callback<F: FnOnce(EventFlags)>(
+    _fd: EvutilSocket,
+    event: std::os::raw::c_short,
+    ctx: EventCallbackCtx,
+) {
+    // libevent frees its own once-event right after this returns, so the
+    // closure is ours to consume.
+    let cb = unsafe { Box::from_raw(ctx as *mut OnceCallback<F>) };
+    cb.base.untrack_once(ctx);
+    cb.base.note_dispatch();
+
+    (cb.inner)(EventFlags::from_bits_truncate(event as u32));
+}
+
+/// Acts as a C-compatible trampoline for the closure given to
+/// `Base::for_each_event`, which continues on to the next event.
+unsafe extern "C" fn handle_foreach_event<F: FnMut(EventInfo)>(
+    _base: *const libevent_sys::event_base,
+    event: *const libevent_sys::event,
+    ctx: *mut c_void,
+) -> c_int {
+    let (f, now) = &mut *(ctx as *mut (F, Duration));
+    f(EventInfo::from_raw(event, *now));
+    0
+}
+
+/// Frees an event spawned via `Base::spawn` which has not finished yet, along
+/// with its closure, as its base is about to be freed.
+unsafe fn free_spawned<T, F>(event: NonNull<libevent_sys::event>) {
+    let ctx = libevent_sys::event_get_callback_arg(event.as_ptr());
+    Base::discard_event_raw::<Internal<T>, T, F>(event, ctx.cast());
+}
+
+/// Frees a closure scheduled via `event_base_once` which never ran.
+unsafe fn free_once_callback<F>(ctx: EventCallbackCtx) {
+    drop(Box::from_raw(ctx as *mut OnceCallback<F>));
+}
+
 impl Base {
     /// Helper for creating a raw event from Inactive.
     fn new_event_raw<S, T: Exec<S, F>, F>(
         &mut self,
         ev: &Event<Inactive<T>>,
-    ) -> Option<NonNull<libevent_sys::event>> {
+    ) -> Option<NonNull<libevent_sys::event>>
+    where
+        Event<S>: CallbackHandle,
+    {
         self.event_new(
             ev.inactive_fd(),
-            ev.inactive_flags(),
+            ev.inactive_flags() | EventFlags::FINALIZE,
             handle_wrapped_callback::<S, T, F>,
             None,
         )
@@ -295,106 +894,601 @@ impl Base {
         &mut self,
         ev: &Event<Inactive<T>>,
         raw_ev: NonNull<libevent_sys::event>,
-        cb_wrapped: Box<EventCallbackWrapper<S, T, F>>,
-    ) -> c_int {
-        // Leak the callback wrapper so we can store it as ctx.
-        let ctx_ptr = NonNull::from(Box::leak(cb_wrapped));
-
-        self.event_assign(
+        ctx: *mut EventCallbackWrapper<S, T, F>,
+    ) -> io::Result<()>
+    where
+        Event<S>: CallbackHandle,
+    {
+        // `FINALIZE` keeps `event_del` from blocking on a callback running on
+        // another thread, as the closure is only freed via `event_finalize`.
+        let ret = self.event_assign(
             raw_ev,
             ev.inactive_fd(),
-            ev.inactive_flags(),
+            ev.inactive_flags() | EventFlags::FINALIZE,
             handle_wrapped_callback::<S, T, F>,
-            Some(ctx_ptr.as_ptr() as EventCallbackCtx),
-        )
-    }
-
-    /// Activates a given inactive `Event` with no handle sharing.
-    ///
-    /// Control of the event via the `Event` handle is relegated only from
-    /// within the closure `F`, which means that no synchronization wrappers
-    /// are required for operation.
-    pub fn spawn<T: Exec<Internal<T>, F>, F>(
-        &mut self,
-        ev: Event<Inactive<T>>,
-        cb: F,
-    ) -> io::Result<()> {
-        // First allocate the event with no context, then apply the reference
-        // to the closure (and itself) later on.
-        let raw_ev = self
-            .new_event_raw::<Internal<T>, T, F>(&ev)
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Failed to allocate event"))?;
-
-        let event: Event<Internal<T>> =
-            EventInner::from_raw(raw_ev, Some(finalize_wrapped_callback::<Internal<T>, T, F>))
-                .into();
-
-        let cb_wrapped = EventCallbackWrapper::new(cb, event);
-
-        // Now we can apply the closure + handle to self.
-        if self.assign_event_raw(&ev, raw_ev, cb_wrapped) != 0 {
+            Some(ctx as EventCallbackCtx),
+        );
+        if ret != 0 {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 "Failed to assign event",
             ));
         }
 
-        if self.event_add(raw_ev, ev.inactive_timeout()) != 0 {
+        if let Some(priority) = ev.spawn_priority() {
+            if self.event_priority_set(raw_ev, priority) != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Failed to set event priority",
+                ));
+            }
+        }
+
+        if self.add_event_raw(raw_ev, ev.inactive_timeout()) != 0 {
             return Err(io::Error::new(io::ErrorKind::Other, "Failed to add event"));
         }
 
         Ok(())
     }
 
-    /// Activates a given inactive `Event` with thread-local sharing.
+    /// Helper for tearing down an event along with its closure right away, for
+    /// events which failed to spawn, or which remain on a base being freed.
     ///
-    /// Control of the event via the `Event` handle is shared between the
-    /// closure `F` as well as the returned `Event`, which internally uses an
-    /// `Rc<RefCell>`.
-    pub fn spawn_local<T: Exec<LocalWeak<T>, F>, F>(
-        &mut self,
-        ev: Event<Inactive<T>>,
-        cb: F,
-    ) -> io::Result<Event<Local<T>>> {
-        // First allocate the event with no context, then apply the reference
-        // to the closure (and itself) later on.
-        let raw_ev = self
-            .new_event_raw::<LocalWeak<T>, T, F>(&ev)
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Failed to allocate event"))?;
+    /// # Safety
+    ///
+    /// `ctx` must be the (leaked) wrapper assigned to `raw_ev`, and the base
+    /// must neither be freed yet, nor be running the event's callback.
+    unsafe fn discard_event_raw<S, T, F>(
+        raw_ev: NonNull<libevent_sys::event>,
+        ctx: *mut EventCallbackWrapper<S, T, F>,
+    ) where
+        Event<S>: CallbackHandle,
+    {
+        let mut cb_wrapped = Box::from_raw(ctx);
+        cb_wrapped.base.untrack_spawned(raw_ev);
 
-        let event: Event<Local<T>> = EventInner::from_raw(
-            raw_ev,
-            Some(finalize_wrapped_callback::<LocalWeak<T>, T, F>),
-        )
-        .into();
-        let closure_event = event.downgrade();
+        // Keeps the handles from finalizing the raw event once dropped.
+        if let Some(event) = cb_wrapped.event.take() {
+            event.discard();
+        }
 
-        let cb_wrapped = EventCallbackWrapper::new(cb, closure_event);
+        // Unregisters the event before the wrapper (and with it an `Io`
+        // source) is dropped, so libevent never watches a closed fd.
+        libevent_sys::event_free(raw_ev.as_ptr());
+        drop(cb_wrapped);
+    }
 
-        // Now we can apply the closure + handle to self.
-        if self.assign_event_raw(&ev, raw_ev, cb_wrapped) != 0 {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "Failed to assign event",
-            ));
-        }
+    /// Helper for adding a raw event with a timeout which may be a common one.
+    fn add_event_raw(
+        &self,
+        raw_ev: NonNull<libevent_sys::event>,
+        timeout: Option<EventTimeout>,
+    ) -> c_int {
+        let tv = timeout.as_ref().map(EventTimeout::to_timeval);
+        let tv_ptr = tv
+            .as_ref()
+            .map_or(std::ptr::null(), |tv| tv as *const libevent_sys::timeval);
 
-        if self.event_add(raw_ev, ev.inactive_timeout()) != 0 {
-            return Err(io::Error::new(io::ErrorKind::Other, "Failed to add event"));
+        unsafe { libevent_sys::event_add(raw_ev.as_ptr(), tv_ptr) }
+    }
+
+    /// Helper for checking that an event's common timeout, if any, was
+    /// initialized on this base, as libevent would misinterpret it otherwise.
+    fn check_timeout<T>(&self, ev: &Event<Inactive<T>>) -> io::Result<()> {
+        match ev.inactive_timeout() {
+            Some(EventTimeout::Common(common)) if !common.belongs_to(&self.state) => {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Common timeout belongs to another event base",
+                ))
+            }
+            _ => Ok(()),
         }
+    }
 
-        Ok(event)
+    /// Helper for checking that an event's priority, if any, is within the
+    /// number of priorities of this base, as `event_priority_set` fails
+    /// otherwise.
+    fn check_priority<T>(&self, ev: &Event<Inactive<T>>) -> io::Result<()> {
+        match ev.spawn_priority() {
+            Some(priority) if priority >= self.num_priorities() => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Event priority exceeds the number of priorities of the event base",
+            )),
+            _ => Ok(()),
+        }
     }
-}
 
-/// Enumerates all possible reasons that the event loop may have stopped
-/// running.
+    /// Helper for checking event flags against what libevent accepts, and what
+    /// the backend in use supports, as libevent either rejects them without
+    /// saying why, or quietly ignores `ET` and `CLOSED`.
+    pub(crate) fn check_flags(&self, flags: EventFlags) -> io::Result<()> {
+        let fd_flags = EventFlags::READ | EventFlags::WRITE | EventFlags::CLOSED;
+        if flags.contains(EventFlags::SIGNAL) && flags.intersects(fd_flags) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "SIGNAL cannot be combined with READ, WRITE or CLOSED",
+            ));
+        }
+
+        let features = self.features();
+
+        if flags.contains(EventFlags::ET) && !features.contains(BackendFeatures::ET) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "Backend `{}` does not support edge-triggered (ET) events",
+                    self.method()
+                ),
+            ));
+        }
+
+        if flags.contains(EventFlags::CLOSED) && !features.contains(BackendFeatures::EARLY_CLOSE) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "Backend `{}` does not support detecting closed connections (CLOSED)",
+                    self.method()
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Helper for claiming signal handling if the event is a signal event.
+    fn claim_signal<T>(&self, ev: &Event<Inactive<T>>) -> io::Result<Option<SignalClaim>> {
+        if ev.inactive_flags().contains(EventFlags::SIGNAL) {
+            SignalClaim::acquire(&self.state).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Helper for the `spawn*` variants, which only differ in how `split`
+    /// divides the event between the handle returned to the caller, and the
+    /// one kept alongside the closure.
+    fn spawn_with<S, T, F, H>(
+        &mut self,
+        mut ev: Event<Inactive<T>>,
+        cb: F,
+        split: impl FnOnce(EventInner<T>) -> (H, Event<S>),
+    ) -> io::Result<H>
+    where
+        T: Exec<S, F> + 'static,
+        F: 'static,
+        Event<S>: CallbackHandle,
+    {
+        self.check_flags(ev.inactive_flags())?;
+        self.check_timeout(&ev)?;
+        self.check_priority(&ev)?;
+        let signal_claim = self.claim_signal(&ev)?;
+
+        // First allocate the event with no context, then apply the reference
+        // to the closure (and itself) later on.
+        let raw_ev = self
+            .new_event_raw::<S, T, F>(&ev)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Failed to allocate event"))?;
+
+        let inner = EventInner::from_raw(
+            raw_ev,
+            Some(finalize_wrapped_callback::<S, T, F>),
+            self.state(),
+        )
+        .with_signal_claim(signal_claim)
+        .with_timeout(ev.inactive_timeout());
+        let (handle, closure_event) = split(inner);
+
+        let kind = ev.take_kind();
+        let cb_wrapped = EventCallbackWrapper::new(cb, kind, closure_event, self.state());
+
+        // Leak the callback wrapper so we can store it as ctx, then apply the
+        // closure + handle to self.
+        let ctx = Box::into_raw(cb_wrapped);
+        if let Err(err) = self.assign_event_raw(&ev, raw_ev, ctx) {
+            unsafe { Self::discard_event_raw(raw_ev, ctx) };
+            return Err(err);
+        }
+
+        Ok(handle)
+    }
+
+    /// Activates a given inactive `Event` with no handle sharing.
+    ///
+    /// Control of the event via the `Event` handle is relegated only from
+    /// within the closure `F`, which means that no synchronization wrappers
+    /// are required for operation.
+    pub fn spawn<T: Exec<Internal<T>, F> + 'static, F: 'static>(
+        &mut self,
+        ev: Event<Inactive<T>>,
+        cb: F,
+    ) -> io::Result<()> {
+        let state = self.state();
+
+        self.spawn_with(ev, cb, |inner| {
+            // Tracked before being added, as another thread may run the loop
+            // (and thus finish the event) as soon as it is.
+            state.track_spawned(unsafe { inner.as_raw() }, free_spawned::<T, F>);
+            ((), Event::<Internal<T>>::from(inner))
+        })
+    }
+
+    /// Activates a given inactive `Event` with thread-local sharing.
+    ///
+    /// Control of the event via the `Event` handle is shared between the
+    /// closure `F` as well as the returned `Event`, which internally uses an
+    /// `Rc<RefCell>`.
+    pub fn spawn_local<T: Exec<LocalWeak<T>, F> + 'static, F: 'static>(
+        &mut self,
+        ev: Event<Inactive<T>>,
+        cb: F,
+    ) -> io::Result<Event<Local<T>>> {
+        self.spawn_with(ev, cb, |inner| {
+            let event: Event<Local<T>> = inner.into();
+            let closure_event = event.downgrade();
+            (event, closure_event)
+        })
+    }
+
+    /// Activates a given inactive `Event` with thread-safe sharing.
+    ///
+    /// Control of the event via the `Event` handle is shared between the
+    /// closure `F` as well as the returned `Event`, which internally uses an
+    /// `Arc<Mutex>`, so that the handle can be used from other threads than
+    /// the one running the loop.
+    ///
+    /// Fails unless the base locks itself (see `is_locking`), which bases
+    /// created via `new` or `with_config` do by default.
+    #[cfg(feature = "threading")]
+    pub fn spawn_shared<T: Exec<SharedWeak<T>, F> + Send + 'static, F: Send + 'static>(
+        &mut self,
+        ev: Event<Inactive<T>>,
+        cb: F,
+    ) -> io::Result<Event<Shared<T>>> {
+        if !self.state.locking {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Event base does not lock itself; initialize thread support first",
+            ));
+        }
+
+        self.spawn_with(ev, cb, |inner| {
+            let event: Event<Shared<T>> = inner.into();
+            let closure_event = event.downgrade();
+            (event, closure_event)
+        })
+    }
+
+    /// Wrapper for libevent's `event_base_once`, which runs `cb` a single time
+    /// once `timeout` has elapsed.
+    ///
+    /// libevent owns the underlying event, so no `Event` handle is returned.
+    /// If the base is freed before the timeout elapses, `cb` is dropped
+    /// without being run.
+    pub fn once_timeout<F: FnOnce() + 'static>(&self, timeout: Duration, cb: F) -> io::Result<()> {
+        self.once_raw(None, EventFlags::TIMEOUT, Some(timeout), move |_flags| cb())
+    }
+
+    /// Wrapper for libevent's `event_base_once`, which runs `cb` a single time
+    /// once `fd` becomes ready for any of `flags`, or once `timeout` has
+    /// elapsed, whichever comes first.
+    ///
+    /// The closure receives the flags the event fired with. `flags` must not
+    /// contain `SIGNAL` or `PERSIST`. If the base is freed before the event
+    /// fires, `cb` is dropped without being run.
+    pub fn once_fd<F: FnOnce(EventFlags) + 'static>(
+        &self,
+        fd: EvutilSocket,
+        flags: EventFlags,
+        timeout: Option<Duration>,
+        cb: F,
+    ) -> io::Result<()> {
+        if flags.intersects(EventFlags::SIGNAL | EventFlags::PERSIST) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "One-off events cannot be signal or persistent events",
+            ));
+        }
+
+        self.once_raw(Some(fd), flags, timeout, cb)
+    }
+
+    /// Helper for scheduling a boxed closure via `event_base_once`.
+    fn once_raw<F: FnOnce(EventFlags) + 'static>(
+        &self,
+        fd: Option<EvutilSocket>,
+        flags: EventFlags,
+        timeout: Option<Duration>,
+        cb: F,
+    ) -> io::Result<()> {
+        let ctx = Box::into_raw(Box::new(OnceCallback {
+            inner: cb,
+            base: self.state(),
+        })) as EventCallbackCtx;
+
+        // Track the closure before handing it over, as another thread may run
+        // the loop (and thus the closure) as soon as it is scheduled.
+        self.state.track_once(ctx, free_once_callback::<F>);
+
+        let tv = timeout.map(to_timeval);
+        let tv_ptr = tv
+            .as_ref()
+            .map_or(std::ptr::null(), |tv| tv as *const libevent_sys::timeval);
+
+        let ret = unsafe {
+            libevent_sys::event_base_once(
+                self.base.as_ptr(),
+                fd.unwrap_or(-1),
+                flags.bits() as c_short,
+                Some(handle_once_callback::<F>),
+                ctx,
+                tv_ptr,
+            )
+        };
+
+        if ret != 0 {
+            self.state.untrack_once(ctx);
+            unsafe { free_once_callback::<F>(ctx) };
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to schedule one-off event",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The context a callback gets via the handle to its own event (see e.g.
+/// `Event::context`), through which it can spawn events on, schedule one-off
+/// callbacks on, and control the loop of the `Base` running it.
+///
+/// It borrows the callback's handle, and thus cannot outlive the callback or
+/// leave its thread.
+pub struct CallbackContext<'a> {
+    base: Base,
+    _handle: std::marker::PhantomData<&'a *const ()>,
+}
+
+impl CallbackContext<'_> {
+    pub(crate) fn new(base: Base) -> Self {
+        CallbackContext {
+            base,
+            _handle: std::marker::PhantomData,
+        }
+    }
+
+    /// Same as `Base::spawn`, on the base running the callback.
+    pub fn spawn<T: Exec<Internal<T>, F> + 'static, F: 'static>(
+        &mut self,
+        ev: Event<Inactive<T>>,
+        cb: F,
+    ) -> io::Result<()> {
+        self.base.spawn(ev, cb)
+    }
+
+    /// Same as `Base::spawn_local`, on the base running the callback.
+    pub fn spawn_local<T: Exec<LocalWeak<T>, F> + 'static, F: 'static>(
+        &mut self,
+        ev: Event<Inactive<T>>,
+        cb: F,
+    ) -> io::Result<Event<Local<T>>> {
+        self.base.spawn_local(ev, cb)
+    }
+
+    /// Same as `Base::spawn_shared`, on the base running the callback.
+    #[cfg(feature = "threading")]
+    pub fn spawn_shared<T: Exec<SharedWeak<T>, F> + Send + 'static, F: Send + 'static>(
+        &mut self,
+        ev: Event<Inactive<T>>,
+        cb: F,
+    ) -> io::Result<Event<Shared<T>>> {
+        self.base.spawn_shared(ev, cb)
+    }
+
+    /// Same as `Base::once_timeout`, on the base running the callback.
+    pub fn once_timeout<F: FnOnce() + 'static>(&self, timeout: Duration, cb: F) -> io::Result<()> {
+        self.base.once_timeout(timeout, cb)
+    }
+
+    /// Same as `Base::once_fd`, on the base running the callback.
+    pub fn once_fd<F: FnOnce(EventFlags) + 'static>(
+        &self,
+        fd: EvutilSocket,
+        flags: EventFlags,
+        timeout: Option<Duration>,
+        cb: F,
+    ) -> io::Result<()> {
+        self.base.once_fd(fd, flags, timeout, cb)
+    }
+
+    /// Same as `Base::loopbreak`, which makes the running loop return right
+    /// after the callback.
+    pub fn loopbreak(&self) -> i32 {
+        self.base.loopbreak()
+    }
+
+    /// Same as `Base::loopexit`, which makes the running loop return after
+    /// `timeout` has elapsed.
+    pub fn loopexit(&self, timeout: Duration) -> i32 {
+        self.base.loopexit(timeout)
+    }
+}
+
+/// The base which currently handles signals within the process, along with
+/// the number of signal events spawned on it.
+///
+/// libevent only delivers signals to a single base at a time, and silently
+/// moves them to whichever base most recently added a signal event.
+static SIGNAL_OWNER: Mutex<Option<(Weak<BaseState>, usize)>> = Mutex::new(None);
+
+/// Registration of a signal event with the base handling signals, which is
+/// released once dropped.
+#[derive(Debug)]
+pub(crate) struct SignalClaim(Arc<BaseState>);
+
+impl SignalClaim {
+    /// Claims signal handling for `base`, failing if signal events have been
+    /// spawned on another live base.
+    pub(crate) fn acquire(base: &Arc<BaseState>) -> io::Result<Self> {
+        let mut owner = SIGNAL_OWNER.lock().unwrap_or_else(|e| e.into_inner());
+
+        match owner.as_mut() {
+            Some((owner_base, count)) if owner_base.as_ptr() == Arc::as_ptr(base) => {
+                *count += 1;
+            }
+            Some((owner_base, _)) if owner_base.upgrade().is_some_and(|owner| owner.is_alive()) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Signals are already handled by another event base",
+                ));
+            }
+            _ => *owner = Some((Arc::downgrade(base), 1)),
+        }
+
+        Ok(SignalClaim(base.clone()))
+    }
+}
+
+impl Drop for SignalClaim {
+    fn drop(&mut self) {
+        let mut owner = SIGNAL_OWNER.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some((owner_base, count)) = owner.as_mut() {
+            if owner_base.as_ptr() == Arc::as_ptr(&self.0) {
+                *count -= 1;
+                if *count == 0 {
+                    *owner = None;
+                }
+            }
+        }
+    }
+}
+
+/// Internal timer which makes the running loop exit once it fires.
+///
+/// Unlike `Base::loopexit`, the timer is cancelled when dropped, so it never
+/// affects runs other than the one it was created for.
+pub(crate) struct ExitTimer(NonNull<libevent_sys::event>);
+
+impl ExitTimer {
+    pub(crate) fn new(base: &Base, timeout: Duration) -> io::Result<Self> {
+        let base_ptr = base.base.as_ptr();
+        let raw = unsafe {
+            libevent_sys::event_new(
+                base_ptr,
+                -1,
+                0,
+                Some(Self::handle_timeout),
+                base_ptr as EventCallbackCtx,
+            )
+        };
+        let timer = NonNull::new(raw)
+            .map(ExitTimer)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Failed to allocate event"))?;
+
+        if base.event_add(timer.0, Some(timeout)) != 0 {
+            return Err(io::Error::new(io::ErrorKind::Other, "Failed to add event"));
+        }
+
+        Ok(timer)
+    }
+
+    extern "C" fn handle_timeout(
+        _fd: EvutilSocket,
+        _flags: EventCallbackFlags,
+        ctx: EventCallbackCtx,
+    ) {
+        let base = ctx as *mut libevent_sys::event_base;
+        unsafe { libevent_sys::event_base_loopexit(base, std::ptr::null()) };
+    }
+}
+
+impl Drop for ExitTimer {
+    fn drop(&mut self) {
+        unsafe { libevent_sys::event_free(self.0.as_ptr()) };
+    }
+}
+
+/// Wrapper for libevent's `event_get_supported_methods`, which lists the
+/// names of all backends available to libevent, in order of preference.
+pub fn supported_methods() -> impl Iterator<Item = &'static str> {
+    // libevent frees the previously returned array on every call, so calls
+    // are serialized and the (static) names are copied out immediately.
+    static LOCK: Mutex<()> = Mutex::new(());
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut methods = Vec::new();
+    unsafe {
+        let mut method = libevent_sys::event_get_supported_methods();
+        while !method.is_null() && !(*method).is_null() {
+            methods.push(CStr::from_ptr(*method).to_str().unwrap_or(""));
+            method = method.add(1);
+        }
+    }
+
+    methods.into_iter()
+}
+
+/// A timeout initialized via `Base::common_timeout`, which events spawned on
+/// that same base can use in place of a plain `Duration`.
+#[derive(Clone, Debug)]
+pub struct CommonTimeout {
+    tv: libevent_sys::timeval,
+    duration: Duration,
+    base: Weak<BaseState>,
+}
+
+impl CommonTimeout {
+    /// The duration of the timeout.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The encoded `timeval` libevent identifies the common timeout by.
+    pub(crate) fn timeval(&self) -> libevent_sys::timeval {
+        self.tv
+    }
+
+    /// Whether the common timeout was initialized on the base with `state`.
+    pub(crate) fn belongs_to(&self, state: &Arc<BaseState>) -> bool {
+        self.base.as_ptr() == Arc::as_ptr(state)
+    }
+}
+
+/// Enumerates all possible reasons that the event loop may have stopped
+/// running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExitReason {
     GotExit,
     GotBreak,
     Error,
     NoPendingEvents,
-    Unknown { flags: LoopFlags, exit_code: i32 },
+    /// The loop finished the single pass requested via `LoopFlags::ONCE` or
+    /// `LoopFlags::NONBLOCK`.
+    Completed,
+    Unknown {
+        flags: LoopFlags,
+        exit_code: i32,
+    },
+}
+
+/// The outcome of running the event loop for a bounded amount of work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopOutcome {
+    /// Why the loop stopped running.
+    pub reason: ExitReason,
+    /// The number of callbacks dispatched to events spawned via this crate
+    /// while the loop was running. Callbacks of events created elsewhere
+    /// (e.g. from C) are not counted.
+    pub dispatched: usize,
+}
+
+impl LoopOutcome {
+    /// Whether any callbacks were dispatched.
+    pub fn did_work(&self) -> bool {
+        self.dispatched > 0
+    }
 }
 
 bitflags! {
@@ -423,3 +1517,910 @@ bitflags! {
         const CLOSED = libevent_sys::EV_CLOSED;
     }
 }
+
+bitflags! {
+    /// Event states counted by `Base::num_events` and `Base::max_events`.
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct EventCountFlags: u32 {
+        const ACTIVE = libevent_sys::EVENT_BASE_COUNT_ACTIVE;
+        const ADDED = libevent_sys::EVENT_BASE_COUNT_ADDED;
+        const VIRTUAL = libevent_sys::EVENT_BASE_COUNT_VIRTUAL;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes tests spawning signal events, which only one base in the
+    /// process may handle at a time.
+    static SIGNALS: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn with_config_applies_flags_and_features() {
+        let mut config = EventConfig::new().unwrap();
+        config
+            .set_flag(BaseConfigFlags::IGNORE_ENV)
+            .unwrap()
+            .set_flag(BaseConfigFlags::NO_CACHE_TIME)
+            .unwrap()
+            .require_features(BackendFeatures::O1)
+            .unwrap()
+            .set_max_dispatch_interval(Some(Duration::from_millis(10)), Some(16), 0)
+            .unwrap();
+        assert_eq!(
+            config.flags(),
+            BaseConfigFlags::IGNORE_ENV | BaseConfigFlags::NO_CACHE_TIME
+        );
+
+        // Every backend libevent builds on Linux (epoll) provides O(1) dispatch.
+        let mut base = Base::with_config(&config).unwrap();
+        assert!(base.features().contains(BackendFeatures::O1));
+
+        let fired = std::rc::Rc::new(std::cell::Cell::new(false));
+        let sink = fired.clone();
+        base.spawn(Oneshot::new(Duration::from_millis(1)), move |_ev| {
+            sink.set(true)
+        })
+        .unwrap();
+        base.run();
+        assert!(fired.get());
+    }
+
+    #[test]
+    fn from_raw_leaves_base_to_caller() {
+        let raw = NonNull::new(unsafe { libevent_sys::event_base_new() }).unwrap();
+
+        let borrowed = unsafe { Base::from_raw(raw) };
+        assert!(borrowed.is_borrowed());
+        drop(borrowed);
+
+        // Still usable, and freed exactly once by the owning `Base`.
+        let mut owned = unsafe { Base::from_raw_owned(raw) };
+        assert!(!owned.is_borrowed());
+        assert!(!owned.is_locking());
+        owned
+            .spawn(Oneshot::new(Duration::from_millis(0)), |_ev| {})
+            .unwrap();
+        assert_eq!(owned.turn().dispatched, 1);
+    }
+
+    #[test]
+    fn with_config_avoids_method() {
+        let preferred = supported_methods().next().expect("No supported methods");
+
+        let mut config = EventConfig::new().unwrap();
+        config.avoid_method(preferred).unwrap();
+
+        match Base::with_config(&config) {
+            Ok(base) => {
+                assert_ne!(base.method(), preferred);
+                assert!(supported_methods().any(|method| method == base.method()));
+            }
+            // Only a single backend was available to begin with.
+            Err(_) => assert_eq!(supported_methods().count(), 1),
+        }
+    }
+
+    #[test]
+    fn with_config_unsatisfiable() {
+        let mut config = EventConfig::new().unwrap();
+        for method in supported_methods() {
+            config.avoid_method(method).unwrap();
+        }
+
+        assert!(Base::with_config(&config).is_err());
+    }
+
+    #[test]
+    fn turn_reports_dispatched() {
+        let mut base = Base::new().unwrap();
+
+        let _idle = base
+            .spawn_local(Interval::new(Duration::from_secs(3600)), |_ev| {})
+            .unwrap();
+        base.spawn(Oneshot::new(Duration::from_secs(0)), |_ev| {})
+            .unwrap();
+
+        let outcome = base.turn();
+        assert_eq!(outcome.reason, ExitReason::Completed);
+        assert_eq!(outcome.dispatched, 1);
+
+        let outcome = base.turn();
+        assert_eq!(outcome.reason, ExitReason::Completed);
+        assert!(!outcome.did_work());
+    }
+
+    #[test]
+    fn run_until_event_cancels_timeout() {
+        let mut base = Base::new().unwrap();
+
+        let _idle = base
+            .spawn_local(Interval::new(Duration::from_secs(3600)), |_ev| {})
+            .unwrap();
+        base.spawn(Oneshot::new(Duration::from_secs(0)), |_ev| {})
+            .unwrap();
+
+        let outcome = base.run_until_event(Some(Duration::from_secs(3600)));
+        assert_eq!(outcome.reason, ExitReason::Completed);
+
+        // Only the idle interval remains; the run's exit timer is gone.
+        assert_eq!(base.num_events(EventCountFlags::ADDED), 1);
+    }
+
+    #[test]
+    fn once_runs_or_is_freed_with_base() {
+        let base = Base::new().unwrap();
+
+        let ran = std::rc::Rc::new(std::cell::Cell::new(false));
+        let flag = ran.clone();
+        base.once_timeout(Duration::from_secs(0), move || flag.set(true))
+            .unwrap();
+
+        let unrun = std::rc::Rc::new(());
+        let guard = unrun.clone();
+        base.once_timeout(Duration::from_secs(3600), move || drop(guard))
+            .unwrap();
+
+        assert_eq!(base.run_until_event(None).dispatched, 1);
+        assert!(ran.get());
+        assert_eq!(std::rc::Rc::strong_count(&ran), 1);
+
+        drop(base);
+        assert_eq!(std::rc::Rc::strong_count(&unrun), 1);
+    }
+
+    #[test]
+    fn oneshot_finishes_after_firing() {
+        let mut base = Base::new().unwrap();
+
+        let moved = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let (sink, values) = (moved.clone(), vec![1, 2, 3]);
+        let mut ev = base
+            .spawn_local(Oneshot::new(Duration::from_secs(0)), move |_ev| {
+                *sink.borrow_mut() = values;
+            })
+            .unwrap();
+        assert!(!ev.is_finished());
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(*moved.borrow(), vec![1, 2, 3]);
+
+        // The closure (and with it, its captures) is gone along with the event.
+        assert!(ev.is_finished());
+        assert_eq!(std::rc::Rc::strong_count(&moved), 1);
+        assert!(ev.activate(EventFlags::TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn reschedule_and_restart() {
+        let mut base = Base::new().unwrap();
+        let hour = Duration::from_secs(3600);
+
+        let mut ev = base.spawn_local(Interval::new(hour), |_ev| {}).unwrap();
+        assert_eq!(ev.interval(), hour);
+        assert!(ev.is_pending(EventFlags::TIMEOUT));
+        assert!(ev.time_remaining().unwrap() > Duration::from_secs(3500));
+
+        ev.reschedule(Duration::from_secs(60)).unwrap();
+        assert!(ev.time_remaining().unwrap() <= Duration::from_secs(60));
+
+        ev.stop().unwrap();
+        assert!(!ev.is_pending(EventFlags::TIMEOUT));
+        assert_eq!(ev.time_remaining(), None);
+
+        ev.restart().unwrap();
+        assert!(ev.time_remaining().unwrap() > Duration::from_secs(3500));
+    }
+
+    #[test]
+    fn restart_after_stop_in_callback() {
+        let mut base = Base::new().unwrap();
+
+        let count = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = count.clone();
+        let mut ev = base
+            .spawn_local(Interval::new(Duration::from_millis(1)), move |ev| {
+                counter.set(counter.get() + 1);
+                ev.stop().unwrap();
+            })
+            .unwrap();
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(count.get(), 1);
+
+        ev.restart().unwrap();
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn pause_keeps_closure() {
+        let mut base = Base::new().unwrap();
+
+        let count = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = count.clone();
+        let mut ev = base
+            .spawn_local(Interval::new(Duration::from_millis(1)), move |ev| {
+                counter.set(counter.get() + 1);
+                ev.pause().unwrap();
+            })
+            .unwrap();
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(count.get(), 1);
+        assert!(!ev.is_pending(EventFlags::TIMEOUT));
+
+        ev.resume().unwrap();
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn set_interest_switches_to_write() {
+        let mut base = Base::new().unwrap();
+
+        let mut fds = [0; 2];
+        let ret =
+            unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_STREAM, 0, fds.as_mut_ptr()) };
+        assert_eq!(ret, 0);
+
+        let fired = std::rc::Rc::new(std::cell::Cell::new(EventFlags::empty()));
+        let sink = fired.clone();
+        let flags = EventFlags::READ | EventFlags::PERSIST;
+        let mut ev = base
+            .spawn_local(Event::new(fds[0], flags, None), move |ev, _fd, flags| {
+                sink.set(flags);
+                ev.pause().unwrap();
+            })
+            .unwrap();
+
+        assert_eq!(ev.fd(), fds[0]);
+        assert!(ev.belongs_to(&base).unwrap());
+        assert!(ev.set_interest(EventFlags::SIGNAL).is_err());
+        ev.set_interest(EventFlags::WRITE | EventFlags::PERSIST)
+            .unwrap();
+        assert_eq!(ev.flags().unwrap(), EventFlags::WRITE | EventFlags::PERSIST);
+        assert!(ev.is_pending(EventFlags::WRITE));
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(fired.get(), EventFlags::WRITE);
+
+        drop(ev);
+        unsafe {
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+    }
+
+    #[test]
+    fn common_timeouts_fire() {
+        let mut base = Base::new().unwrap();
+        let common = base.common_timeout(Duration::from_millis(1)).unwrap();
+        assert_eq!(common.duration(), Duration::from_millis(1));
+
+        let count = std::rc::Rc::new(std::cell::Cell::new(0));
+        for _ in 0..3 {
+            let counter = count.clone();
+            base.spawn(Oneshot::new(&common), move |_ev| {
+                counter.set(counter.get() + 1)
+            })
+            .unwrap();
+        }
+
+        let mut ev = base.spawn_local(Interval::new(&common), |_ev| {}).unwrap();
+        assert_eq!(ev.interval(), Duration::from_millis(1));
+        assert!(ev.time_remaining().unwrap() <= Duration::from_millis(1));
+        ev.stop().unwrap();
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(count.get(), 3);
+
+        let mut other = Base::new().unwrap();
+        assert!(other.spawn(Oneshot::new(&common), |_ev| {}).is_err());
+    }
+
+    #[test]
+    fn dropped_event_is_finalized_by_loop() {
+        let mut base = Base::new().unwrap();
+
+        let captured = std::rc::Rc::new(());
+        let guard = captured.clone();
+        let ev = base
+            .spawn_local(Interval::new(Duration::from_secs(3600)), move |_ev| {
+                let _ = &guard;
+            })
+            .unwrap();
+
+        // The closure is only dropped once libevent runs the finalizer.
+        drop(ev);
+        assert_eq!(std::rc::Rc::strong_count(&captured), 2);
+
+        base.turn();
+        assert_eq!(std::rc::Rc::strong_count(&captured), 1);
+    }
+
+    #[test]
+    fn spawn_checks_flags() {
+        use std::os::unix::io::AsRawFd;
+        use std::os::unix::net::UnixStream;
+
+        // Prefer a backend without ET and CLOSED support, such as `poll`.
+        let mut config = EventConfig::new().unwrap();
+        config.avoid_method("epoll").unwrap();
+        let mut base = Base::with_config(&config).or_else(|_| Base::new()).unwrap();
+        let (ours, _theirs) = UnixStream::pair().unwrap();
+
+        let err = base
+            .spawn(
+                Event::new(
+                    ours.as_raw_fd(),
+                    EventFlags::SIGNAL | EventFlags::READ,
+                    None,
+                ),
+                |_ev, _fd, _flags| {},
+            )
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        for (flag, feature) in [
+            (EventFlags::ET, BackendFeatures::ET),
+            (EventFlags::CLOSED, BackendFeatures::EARLY_CLOSE),
+        ] {
+            let ev = Event::new(ours.as_raw_fd(), EventFlags::READ | flag, None);
+            match base.spawn(ev, |_ev, _fd, _flags| {}) {
+                Ok(()) => assert!(base.features().contains(feature)),
+                Err(err) => {
+                    assert!(!base.features().contains(feature));
+                    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn events_can_be_listed_and_dumped() {
+        let mut base = Base::new().unwrap();
+
+        let _timer = base
+            .spawn_local(Interval::new(Duration::from_secs(3600)), |_ev| {})
+            .unwrap();
+
+        // Bases exist already, so it is too late for debug mode.
+        if !crate::debug::is_debug_mode_enabled() {
+            assert!(crate::debug::enable_debug_mode().is_err());
+        }
+
+        let mut infos = Vec::new();
+        base.for_each_event(|info| infos.push(info)).unwrap();
+        // libevent may list its own event for waking up the loop as well.
+        let timer = infos.iter().find(|info| info.fd() == -1).unwrap();
+        assert_eq!(timer.flags(), EventFlags::PERSIST);
+        let timeout = timer.timeout().unwrap();
+        assert!(timeout > Duration::from_secs(3500) && timeout <= Duration::from_secs(3600));
+
+        let mut dump = Vec::new();
+        base.dump_events(&mut dump).unwrap();
+        assert!(String::from_utf8(dump).unwrap().contains("Inserted events"));
+    }
+
+    #[test]
+    fn fd_closures_take_readiness() {
+        use std::os::unix::io::AsRawFd;
+        use std::os::unix::net::UnixStream;
+
+        let mut base = Base::new().unwrap();
+
+        let (ours, theirs) = UnixStream::pair().unwrap();
+        let seen = std::rc::Rc::new(std::cell::Cell::new(None));
+        let sink = seen.clone();
+        base.spawn(
+            Event::new(ours.as_raw_fd(), EventFlags::WRITE, None).with_readiness(),
+            move |ev, _fd, ready| {
+                sink.set(Some(ready));
+                ev.stop().unwrap();
+            },
+        )
+        .unwrap();
+
+        base.turn();
+        let ready = seen.get().unwrap();
+        assert!(ready.writable());
+        assert!(!ready.readable() && !ready.timed_out() && !ready.closed());
+
+        let seen = std::rc::Rc::new(std::cell::Cell::new(None));
+        let sink = seen.clone();
+        base.spawn(
+            Io::new(theirs, EventFlags::WRITE, None).with_readiness(),
+            move |ev, _source, ready| {
+                sink.set(Some(ready));
+                ev.stop().unwrap();
+            },
+        )
+        .unwrap();
+
+        base.turn();
+        assert!(seen.get().unwrap().writable());
+    }
+
+    #[test]
+    fn io_event_closes_source_after_free() {
+        use std::io::{Read, Write};
+        use std::os::unix::net::UnixStream;
+
+        let mut base = Base::new().unwrap();
+
+        let (ours, mut theirs) = UnixStream::pair().unwrap();
+        theirs.set_nonblocking(true).unwrap();
+        theirs.write_all(b"ping").unwrap();
+
+        let received = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = received.clone();
+        let ev = base
+            .spawn_local(
+                Io::new(ours, EventFlags::READ | EventFlags::PERSIST, None),
+                move |_ev, stream: &mut UnixStream, _flags| {
+                    let mut buf = [0u8; 16];
+                    let n = stream.read(&mut buf).unwrap();
+                    sink.borrow_mut().extend_from_slice(&buf[..n]);
+                    Control::Stop
+                },
+            )
+            .unwrap();
+
+        base.turn();
+        assert_eq!(&*received.borrow(), b"ping");
+
+        // Still open while the handle keeps the event around.
+        let mut buf = [0u8; 1];
+        let err = theirs.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+        drop(ev);
+        base.turn();
+        assert_eq!(theirs.read(&mut buf).unwrap(), 0);
+    }
+
+    /// Whether `fd` is watched by any epoll instance of this process.
+    fn registered_in_epoll(fd: std::os::unix::io::RawFd) -> bool {
+        let watched = format!("tfd: {:>8}", fd);
+        std::fs::read_dir("/proc/self/fdinfo")
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+            .any(|info| info.lines().any(|line| line.starts_with(&watched)))
+    }
+
+    #[test]
+    fn io_event_unregistered_before_base_drop_closes_source() {
+        use std::os::unix::io::{AsRawFd, RawFd};
+        use std::os::unix::net::UnixStream;
+
+        struct Watched(UnixStream, std::rc::Rc<std::cell::Cell<Option<bool>>>);
+
+        impl AsRawFd for Watched {
+            fn as_raw_fd(&self) -> RawFd {
+                self.0.as_raw_fd()
+            }
+        }
+
+        impl Drop for Watched {
+            fn drop(&mut self) {
+                self.1.set(Some(registered_in_epoll(self.0.as_raw_fd())));
+            }
+        }
+
+        let mut base = Base::new().unwrap();
+
+        let (ours, _theirs) = UnixStream::pair().unwrap();
+        let fd = ours.as_raw_fd();
+        let registered = std::rc::Rc::new(std::cell::Cell::new(None));
+        base.spawn(
+            Io::new(
+                Watched(ours, registered.clone()),
+                EventFlags::READ | EventFlags::PERSIST,
+                None,
+            ),
+            |_ev, _source, _flags| {},
+        )
+        .unwrap();
+
+        if base.method() == "epoll" {
+            assert!(registered_in_epoll(fd));
+        }
+
+        drop(base);
+        assert_eq!(registered.get(), Some(false));
+    }
+
+    #[test]
+    fn drop_own_handle_in_callback() {
+        let mut base = Base::new().unwrap();
+
+        let slot = std::rc::Rc::new(std::cell::RefCell::new(None));
+        let own = slot.clone();
+        let ev = base
+            .spawn_local(Interval::new(Duration::from_millis(1)), move |ev| {
+                // Drops the last handle while the closure is still running.
+                drop(own.borrow_mut().take());
+
+                assert!(ev.is_finished());
+                assert!(ev.activate(EventFlags::TIMEOUT).is_err());
+                assert_eq!(ev.time_remaining(), None);
+            })
+            .unwrap();
+        *slot.borrow_mut() = Some(ev);
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert!(slot.borrow().is_none());
+        assert_eq!(std::rc::Rc::strong_count(&slot), 1);
+    }
+
+    #[test]
+    fn drop_own_handle_before_turning_other_base() {
+        let mut base = Base::new().unwrap();
+        let mut other = Base::new().unwrap();
+
+        let runs = std::rc::Rc::new(std::cell::Cell::new(0));
+        let count = runs.clone();
+        let slot = std::rc::Rc::new(std::cell::RefCell::new(None));
+        let own = slot.clone();
+        let ev = base
+            .spawn_local(Interval::new(Duration::from_millis(1)), move |_ev| {
+                count.set(count.get() + 1);
+                drop(own.borrow_mut().take());
+
+                // A callback running on the other base in the meantime must
+                // not lose track of this event, which is finalized once this
+                // closure returns.
+                other
+                    .spawn(Oneshot::new(Duration::from_millis(0)), |_ev| {})
+                    .unwrap();
+                other.turn();
+            })
+            .unwrap();
+        *slot.borrow_mut() = Some(ev);
+
+        base.run_timeout(Duration::from_millis(50));
+        assert_eq!(runs.get(), 1);
+    }
+
+    #[test]
+    fn drop_other_handle_in_callback() {
+        let mut base = Base::new().unwrap();
+
+        // Each closure is the sole owner of the other event's only handle, so
+        // the first to run drops the other event, which in turn drops it.
+        let slots = [(); 2].map(|_| std::rc::Rc::new(std::cell::RefCell::new(None)));
+        let watched = [
+            std::rc::Rc::downgrade(&slots[0]),
+            std::rc::Rc::downgrade(&slots[1]),
+        ];
+
+        let mut events = Vec::new();
+        for other in slots.into_iter().rev() {
+            let ev = base
+                .spawn_local(Interval::new(Duration::from_millis(1)), move |_ev| {
+                    drop(other.borrow_mut().take());
+                })
+                .unwrap();
+            events.push(ev);
+        }
+        for (slot, ev) in watched.iter().zip(events) {
+            *slot.upgrade().unwrap().borrow_mut() = Some(ev);
+        }
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert!(watched.iter().all(|slot| slot.upgrade().is_none()));
+    }
+
+    #[test]
+    fn trigger_runs_once_per_activation() {
+        let mut base = Base::new().unwrap();
+
+        let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = seen.clone();
+        let mut ev = base
+            .spawn_local(Trigger::new(), move |_ev, flags| {
+                sink.borrow_mut().push(flags)
+            })
+            .unwrap();
+
+        // Without an fd or timeout, nothing keeps the loop running.
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert!(seen.borrow().is_empty());
+
+        let activations = [EventFlags::READ, EventFlags::WRITE, EventFlags::TIMEOUT];
+        for flags in activations {
+            ev.activate(flags).unwrap();
+            assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        }
+        assert_eq!(*seen.borrow(), activations);
+    }
+
+    #[test]
+    fn callbacks_return_control() {
+        let mut base = Base::new().unwrap();
+
+        let count = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = count.clone();
+        let mut ev = base
+            .spawn_local(Interval::new(Duration::from_secs(3600)), move |_ev| {
+                counter.set(counter.get() + 1);
+                if counter.get() < 3 {
+                    Control::RescheduleIn(Duration::from_millis(1))
+                } else {
+                    Control::Stop
+                }
+            })
+            .unwrap();
+        ev.activate(EventFlags::TIMEOUT).unwrap();
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(count.get(), 3);
+        assert!(!ev.is_pending(EventFlags::TIMEOUT));
+
+        // Stopping via `Control` leaves the event to be added back.
+        ev.restart().unwrap();
+        assert!(ev.is_pending(EventFlags::TIMEOUT));
+        ev.activate(EventFlags::TIMEOUT).unwrap();
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        assert_eq!(count.get(), 4);
+    }
+
+    #[test]
+    fn callbacks_reach_base_through_context() {
+        let mut base = Base::new().unwrap();
+
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = log.clone();
+        base.spawn(Oneshot::new(Duration::from_secs(0)), move |ev| {
+            let mut ctx = ev.context().unwrap();
+
+            let spawned = sink.clone();
+            ctx.spawn(Oneshot::new(Duration::from_secs(0)), move |_ev| {
+                spawned.borrow_mut().push("spawned")
+            })
+            .unwrap();
+
+            let once = sink.clone();
+            ctx.once_timeout(Duration::from_secs(0), move || {
+                once.borrow_mut().push("once")
+            })
+            .unwrap();
+        })
+        .unwrap();
+
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        log.borrow_mut().sort();
+        assert_eq!(*log.borrow(), ["once", "spawned"]);
+
+        let _ev = base
+            .spawn_local(Interval::new(Duration::from_millis(1)), |ev| {
+                ev.context().unwrap().loopbreak();
+            })
+            .unwrap();
+        assert_eq!(base.run(), ExitReason::GotBreak);
+    }
+
+    #[test]
+    fn priorities_order_dispatch() {
+        let mut base = Base::new().unwrap();
+        base.init_priorities(2).unwrap();
+        assert_eq!(base.num_priorities(), 2);
+
+        let order = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        for priority in [1, 0] {
+            let order = order.clone();
+            let ev = Oneshot::new(Duration::from_secs(0)).priority(priority);
+            base.spawn(ev, move |_ev| order.borrow_mut().push(priority))
+                .unwrap();
+        }
+
+        base.turn();
+        assert_eq!(*order.borrow(), vec![0, 1]);
+    }
+
+    #[test]
+    fn failed_spawn_frees_closure() {
+        let mut base = Base::new().unwrap();
+
+        let captured = std::rc::Rc::new(());
+        let guard = captured.clone();
+        let ev = Oneshot::new(Duration::from_secs(0)).priority(50);
+        let err = base.spawn(ev, move |_ev| drop(guard)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(std::rc::Rc::strong_count(&captured), 1);
+
+        // An fd far beyond any open one makes `epoll_ctl`, and thus `event_add`,
+        // fail only once the closure has been handed to libevent.
+        let guard = captured.clone();
+        let result = base.spawn_local(
+            Event::new(1 << 20, EventFlags::READ, None),
+            move |_ev, _fd, _flags| {
+                let _ = &guard;
+            },
+        );
+        if base.method() == "epoll" {
+            assert!(result.is_err());
+        }
+        drop(result);
+        drop(base);
+        assert_eq!(std::rc::Rc::strong_count(&captured), 1);
+    }
+
+    #[test]
+    fn handles_report_spawn_settings() {
+        let _signals = SIGNALS.lock().unwrap_or_else(|e| e.into_inner());
+        let mut base = Base::new().unwrap();
+        base.init_priorities(3).unwrap();
+        let hour = Duration::from_secs(3600);
+
+        let inactive = Interval::new(hour).priority(2);
+        assert_eq!(inactive.interval(), hour);
+        assert_eq!(inactive.flags(), EventFlags::PERSIST);
+        assert_eq!(inactive.spawn_priority(), Some(2));
+        let interval = base.spawn_local(inactive, |_ev| {}).unwrap();
+        assert_eq!(interval.interval(), hour);
+        assert_eq!(interval.priority().unwrap(), 2);
+
+        let inactive = Signal::new(libc::SIGUSR1);
+        assert_eq!(inactive.signal(), libc::SIGUSR1);
+        assert_eq!(inactive.flags(), EventFlags::SIGNAL | EventFlags::PERSIST);
+        assert_eq!(inactive.spawn_priority(), None);
+        let signal = base.spawn_local(inactive, |_ev, _signal| {}).unwrap();
+        assert_eq!(signal.signal(), libc::SIGUSR1);
+        // Without one given, libevent picks the middle priority.
+        assert_eq!(signal.priority().unwrap(), 1);
+
+        let inactive = Event::new(0, EventFlags::READ, Some(hour));
+        assert_eq!(inactive.fd(), 0);
+        assert_eq!(inactive.flags(), EventFlags::READ);
+
+        let (ours, _theirs) = std::os::unix::net::UnixStream::pair().unwrap();
+        let fd = std::os::unix::io::AsRawFd::as_raw_fd(&ours);
+        assert_eq!(Io::new(ours, EventFlags::WRITE, None).fd(), fd);
+    }
+
+    #[test]
+    fn signals_owned_by_one_base() {
+        let _signals = SIGNALS.lock().unwrap_or_else(|e| e.into_inner());
+        let mut base = Base::new().unwrap();
+        let mut other = Base::new().unwrap();
+
+        let received = std::rc::Rc::new(std::cell::Cell::new(None));
+        let closure_received = received.clone();
+        let ev = base
+            .spawn_local(Signal::new(libc::SIGUSR2), move |_ev, signal| {
+                closure_received.set(Some(signal));
+            })
+            .unwrap();
+
+        assert!(other
+            .spawn_local(Signal::new(libc::SIGUSR2), |_ev, _signal| {})
+            .is_err());
+
+        unsafe { libc::raise(libc::SIGUSR2) };
+        base.run_until_event(Some(Duration::from_secs(5)));
+        assert_eq!(received.get(), Some(libc::SIGUSR2));
+
+        drop(ev);
+        assert!(other
+            .spawn_local(Signal::new(libc::SIGUSR2), |_ev, _signal| {})
+            .is_ok());
+    }
+
+    #[cfg(feature = "threading")]
+    #[test]
+    fn shared_activate_from_other_thread() {
+        let mut base = Base::new().unwrap();
+        assert!(base.is_locking());
+
+        let ev = base
+            .spawn_shared(Interval::new(Duration::from_secs(3600)), |ev| {
+                ev.stop().unwrap();
+            })
+            .unwrap();
+
+        let activator = std::thread::spawn(move || {
+            let mut ev = ev;
+            std::thread::sleep(Duration::from_millis(50));
+            ev.activate(EventFlags::TIMEOUT).unwrap();
+            ev
+        });
+
+        // The loop only runs out of events once the other thread woke it up.
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+        drop(activator.join().unwrap());
+    }
+
+    #[cfg(feature = "threading")]
+    #[test]
+    fn shared_stop_from_other_thread() {
+        let mut base = Base::new().unwrap();
+
+        let ev = base
+            .spawn_shared(Interval::new(Duration::from_secs(3600)), |_ev| {})
+            .unwrap();
+        assert!(ev.is_pending(EventFlags::TIMEOUT));
+
+        let mut remote = ev.clone();
+        std::thread::spawn(move || remote.stop().unwrap())
+            .join()
+            .unwrap();
+        assert!(!ev.is_pending(EventFlags::TIMEOUT));
+        assert_eq!(base.run(), ExitReason::NoPendingEvents);
+
+        let mut config = EventConfig::new().unwrap();
+        config.set_flag(BaseConfigFlags::NOLOCK).unwrap();
+        let mut unlocked = Base::with_config(&config).unwrap();
+        assert!(unlocked
+            .spawn_shared(Interval::new(Duration::from_secs(3600)), |_ev| {})
+            .is_err());
+    }
+
+    #[cfg(feature = "threading")]
+    #[test]
+    fn shared_handle_used_while_base_dropped() {
+        let mut base = Base::new().unwrap();
+
+        let ev = base
+            .spawn_shared(Interval::new(Duration::from_secs(3600)), |_ev| {})
+            .unwrap();
+
+        // The base is freed while the other thread keeps handing the event to
+        // libevent, which must then fail rather than reach into freed memory.
+        let mut remote = ev.clone();
+        let worker = std::thread::spawn(move || {
+            let mut calls = 0;
+            while remote.activate(EventFlags::TIMEOUT).is_ok() {
+                let _ = remote.is_pending(EventFlags::TIMEOUT);
+                let _ = remote.time_remaining();
+                calls += 1;
+            }
+            calls
+        });
+
+        std::thread::sleep(Duration::from_millis(10));
+        drop(base);
+
+        assert!(worker.join().unwrap() > 0);
+        assert!(!ev.is_pending(EventFlags::TIMEOUT));
+    }
+
+    #[test]
+    fn event_outlives_base() {
+        let mut base = Base::new().unwrap();
+
+        let captured = std::rc::Rc::new(());
+        let closure_captured = captured.clone();
+        let mut ev = base
+            .spawn_local(Interval::new(Duration::from_secs(1)), move |_ev| {
+                let _ = &closure_captured;
+            })
+            .unwrap();
+
+        // Without a handle, the closure goes along with the base.
+        let spawned = std::rc::Rc::new(());
+        let closure_spawned = spawned.clone();
+        base.spawn(Interval::new(Duration::from_secs(1)), move |_ev| {
+            let _ = &closure_spawned;
+        })
+        .unwrap();
+        let stopped = spawned.clone();
+        base.spawn(Interval::new(Duration::from_millis(1)), move |_ev| {
+            let _ = &stopped;
+            Control::Stop
+        })
+        .unwrap();
+        base.run_until_event(None);
+
+        drop(base);
+        assert_eq!(std::rc::Rc::strong_count(&spawned), 1);
+
+        assert!(ev.stop().is_err());
+        drop(ev);
+        assert_eq!(std::rc::Rc::strong_count(&captured), 1);
+    }
+}
@@ -0,0 +1,179 @@
+use bitflags::bitflags;
+use std::ffi::CString;
+use std::io;
+use std::os::raw::c_int;
+use std::ptr::NonNull;
+use std::time::Duration;
+
+use crate::base::to_timeval;
+
+/// Wrapper for libevent's `event_config`, which describes the requirements
+/// used when creating a new `Base` via [Base::with_config].
+///
+/// [Base::with_config]: struct.Base.html#method.with_config
+#[derive(Debug)]
+pub struct EventConfig {
+    inner: NonNull<libevent_sys::event_config>,
+    flags: BaseConfigFlags,
+}
+
+impl EventConfig {
+    /// Creates a new, empty `EventConfig`.
+    pub fn new() -> io::Result<Self> {
+        let inner = unsafe { libevent_sys::event_config_new() };
+
+        NonNull::new(inner)
+            .map(|inner| EventConfig {
+                inner,
+                flags: BaseConfigFlags::empty(),
+            })
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Failed to create libevent config"))
+    }
+
+    /// Exposes the raw, non-null `event_config` pointer.
+    ///
+    /// # Safety
+    ///
+    /// The pointer is only valid for the lifetime of this `EventConfig`, and
+    /// must not be freed by the caller.
+    pub unsafe fn as_raw(&self) -> NonNull<libevent_sys::event_config> {
+        self.inner
+    }
+
+    /// The flags set so far via `set_flag`.
+    pub fn flags(&self) -> BaseConfigFlags {
+        self.flags
+    }
+
+    /// Wrapper for libevent's `event_config_avoid_method`, which prevents the
+    /// named backend (e.g. `"epoll"`, `"poll"`, `"select"`) from being used.
+    pub fn avoid_method(&mut self, method: &str) -> io::Result<&mut Self> {
+        let method =
+            CString::new(method).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let ret = unsafe {
+            libevent_sys::event_config_avoid_method(self.inner.as_ptr(), method.as_ptr())
+        };
+
+        if ret != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to avoid event method",
+            ));
+        }
+
+        Ok(self)
+    }
+
+    /// Wrapper for libevent's `event_config_require_features`, which only
+    /// allows backends providing all of the given `features`.
+    ///
+    /// Calling this more than once replaces the previously required features.
+    pub fn require_features(&mut self, features: BackendFeatures) -> io::Result<&mut Self> {
+        let ret = unsafe {
+            libevent_sys::event_config_require_features(
+                self.inner.as_ptr(),
+                features.bits() as c_int,
+            )
+        };
+
+        if ret != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to require backend features",
+            ));
+        }
+
+        Ok(self)
+    }
+
+    /// Wrapper for libevent's `event_config_set_flag`, which adds the given
+    /// `flags` to those already set on the config.
+    pub fn set_flag(&mut self, flags: BaseConfigFlags) -> io::Result<&mut Self> {
+        let ret = unsafe {
+            libevent_sys::event_config_set_flag(self.inner.as_ptr(), flags.bits() as c_int)
+        };
+
+        if ret != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to set config flag",
+            ));
+        }
+
+        self.flags |= flags;
+        Ok(self)
+    }
+
+    /// Wrapper for libevent's `event_config_set_max_dispatch_interval`, which
+    /// makes the loop check for new events after `max_interval` has elapsed
+    /// or `max_callbacks` have run, for callbacks at or above `min_priority`.
+    ///
+    /// `None` means no limit for either bound.
+    pub fn set_max_dispatch_interval(
+        &mut self,
+        max_interval: Option<Duration>,
+        max_callbacks: Option<u32>,
+        min_priority: u32,
+    ) -> io::Result<&mut Self> {
+        let tv = max_interval.map(to_timeval);
+        let tv_ptr = tv
+            .as_ref()
+            .map_or(std::ptr::null(), |tv| tv as *const libevent_sys::timeval);
+
+        // libevent treats any negative value as "no limit".
+        let max_callbacks = max_callbacks.map_or(-1, |n| n.min(c_int::MAX as u32) as c_int);
+
+        let ret = unsafe {
+            libevent_sys::event_config_set_max_dispatch_interval(
+                self.inner.as_ptr(),
+                tv_ptr,
+                max_callbacks,
+                min_priority.min(c_int::MAX as u32) as c_int,
+            )
+        };
+
+        if ret != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to set max dispatch interval",
+            ));
+        }
+
+        Ok(self)
+    }
+}
+
+impl Drop for EventConfig {
+    fn drop(&mut self) {
+        unsafe { libevent_sys::event_config_free(self.inner.as_ptr()) };
+    }
+}
+
+bitflags! {
+    /// Features a backend may provide, used both to require a backend via
+    /// `EventConfig` and to describe the backend chosen by a `Base`.
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct BackendFeatures: u32 {
+        const ET = libevent_sys::event_method_feature_EV_FEATURE_ET;
+        const O1 = libevent_sys::event_method_feature_EV_FEATURE_O1;
+        const FDS = libevent_sys::event_method_feature_EV_FEATURE_FDS;
+        const EARLY_CLOSE = libevent_sys::event_method_feature_EV_FEATURE_EARLY_CLOSE;
+    }
+}
+
+bitflags! {
+    /// Flags given to `EventConfig` to alter how a `Base` is constructed.
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct BaseConfigFlags: u32 {
+        const NOLOCK = libevent_sys::event_base_config_flag_EVENT_BASE_FLAG_NOLOCK;
+        const IGNORE_ENV = libevent_sys::event_base_config_flag_EVENT_BASE_FLAG_IGNORE_ENV;
+        const STARTUP_IOCP = libevent_sys::event_base_config_flag_EVENT_BASE_FLAG_STARTUP_IOCP;
+        const NO_CACHE_TIME = libevent_sys::event_base_config_flag_EVENT_BASE_FLAG_NO_CACHE_TIME;
+        const EPOLL_USE_CHANGELIST =
+            libevent_sys::event_base_config_flag_EVENT_BASE_FLAG_EPOLL_USE_CHANGELIST;
+        const EPOLL_DISALLOW_CHANGELIST =
+            libevent_sys::event_base_config_flag_EVENT_BASE_FLAG_EPOLL_DISALLOW_CHANGELIST;
+        const PRECISE_TIMER = libevent_sys::event_base_config_flag_EVENT_BASE_FLAG_PRECISE_TIMER;
+    }
+}
@@ -0,0 +1,129 @@
+//! Access to libevent's debugging aids, for finding out which events are
+//! registered with a `Base` (e.g. when its loop stalls) without attaching a
+//! debugger.
+//!
+//! Besides [enable_debug_mode], see [Base::dump_events] and
+//! [Base::for_each_event].
+//!
+//! [enable_debug_mode]: fn.enable_debug_mode.html
+//! [Base::dump_events]: ../struct.Base.html#method.dump_events
+//! [Base::for_each_event]: ../struct.Base.html#method.for_each_event
+
+use std::io;
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::base::{any_base_created, from_timeval};
+use crate::EventFlags;
+
+/// Masks out the bits libevent stores in the microseconds of an event's
+/// timeout to mark it as a common timeout (see `event-internal.h`).
+const MICROSECONDS_MASK: u32 = 0x000f_ffff;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ENABLE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Wrapper for libevent's `event_enable_debug_mode`, which makes libevent
+/// keep track of every event, so that it aborts on common misuse instead of
+/// corrupting memory (e.g. adding an event which was never assigned).
+///
+/// Must be called before any `Base` is created, as libevent does not know of
+/// events created earlier. Calling it again once enabled is harmless.
+pub fn enable_debug_mode() -> io::Result<()> {
+    let _guard = ENABLE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    // libevent aborts if debug mode is enabled twice.
+    if ENABLED.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    if any_base_created() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Debug mode must be enabled before any event base is created",
+        ));
+    }
+
+    unsafe { libevent_sys::event_enable_debug_mode() };
+
+    ENABLED.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Whether debug mode has been enabled via `enable_debug_mode`.
+pub fn is_debug_mode_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// A snapshot of an event registered with a `Base`, as handed to the closure
+/// of `Base::for_each_event`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventInfo {
+    fd: RawFd,
+    flags: EventFlags,
+    timeout: Option<Duration>,
+}
+
+impl EventInfo {
+    /// Reads the details of a raw `event`, given the time on libevent's
+    /// monotonic clock (see `monotonic_now`).
+    ///
+    /// libevent holds the lock of the base while iterating over its events, so
+    /// this must not call anything taking it again (such as `event_pending`),
+    /// and reads the timeout off the `event` itself instead.
+    ///
+    /// # Safety
+    ///
+    /// `ev` must be valid, as it is while libevent iterates over the events of
+    /// its base.
+    pub(crate) unsafe fn from_raw(ev: *const libevent_sys::event, now: Duration) -> Self {
+        let fd = libevent_sys::event_get_fd(ev);
+        let flags = EventFlags::from_bits_truncate(libevent_sys::event_get_events(ev) as u32)
+            - EventFlags::FINALIZE;
+
+        let ev = &*ev;
+        let timeout = if ev.ev_evcallback.evcb_flags as u32 & libevent_sys::EVLIST_TIMEOUT != 0 {
+            let mut expiry = ev.ev_timeout;
+            expiry.tv_usec = (expiry.tv_usec as u32 & MICROSECONDS_MASK) as _;
+
+            Some(from_timeval(expiry).saturating_sub(now))
+        } else {
+            None
+        };
+
+        EventInfo { fd, flags, timeout }
+    }
+
+    /// The current time on the monotonic clock libevent keeps timeouts on.
+    ///
+    /// libevent may use a coarser variant of the clock, so timeouts computed
+    /// from this can be off by a few milliseconds.
+    pub(crate) fn monotonic_now() -> Duration {
+        let mut now = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now) };
+
+        Duration::new(now.tv_sec as u64, now.tv_nsec as u32)
+    }
+
+    /// The file descriptor the event watches, the signal number for signal
+    /// events, or -1 for events with neither.
+    pub fn fd(&self) -> RawFd {
+        self.fd
+    }
+
+    /// The flags the event was created with.
+    pub fn flags(&self) -> EventFlags {
+        self.flags
+    }
+
+    /// The time left until the event's timeout expires, or `None` if no
+    /// timeout is pending. Only accurate to a few milliseconds.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
@@ -1,20 +1,40 @@
+use crate::base::{
+    defer_finalize, from_timeval, to_timeval, BaseState, CommonTimeout, SignalClaim,
+};
 use crate::EventFlags;
+use crate::{Base, CallbackContext};
 use std::cell::RefCell;
 use std::io;
 use std::marker::PhantomData;
-use std::os::unix::io::RawFd;
+use std::os::raw::{c_int, c_short};
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::ptr::NonNull;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 /// The primitive event-type which is created with [Event::new] using a
 /// a non-negative `RawFd`.
 ///
+/// `A` is what its closure is handed for the conditions the event fired on:
+/// the raw `EventFlags` by default, or a `Readiness` once switched over via
+/// `with_readiness`.
+///
 /// [Event::new]: struct.Event.html#method.new
 #[derive(Debug)]
-pub struct Fd;
+pub struct Fd<A = EventFlags>(PhantomData<A>);
+
+/// An fd event-type which owns its I/O source (e.g., a `TcpStream`), and is
+/// created with [Io::new].
+///
+/// Its closure is handed `&mut Src` in place of the `RawFd`, and `A` as for
+/// `Fd`. The source is only dropped, and thus closed, once the raw `event`
+/// has been freed, so libevent never watches a closed (or reused) fd.
+///
+/// [Io::new]: struct.Io.html#method.new
+#[derive(Debug)]
+pub struct Io<Src, A = EventFlags>(Src, PhantomData<A>);
 
 /// A specialized event-type which represents a continuous-interval timer.
 #[derive(Debug)]
@@ -22,18 +42,181 @@ pub struct Interval;
 
 /// A specialized event-type which represents a one-time event that cleans
 /// itself up after execution.
+///
+/// Its closure is an `FnOnce`, and both it and the raw `event` are freed as
+/// soon as it returns, after which any remaining handles report
+/// `is_finished`.
 #[derive(Debug)]
 pub struct Oneshot;
 
+/// A specialized event-type which fires each time the process receives a
+/// given signal.
+///
+/// Only one `Base` within the process can handle signals at a time.
+#[derive(Debug)]
+pub struct Signal;
+
+/// A specialized event-type which has neither a file descriptor nor a
+/// timeout, and only fires when explicitly activated via its handle.
+#[derive(Debug)]
+pub struct Trigger;
+
 impl Interval {
-    pub fn new(interval: Duration) -> Event<Inactive<Interval>> {
-        Inactive::new(None, EventFlags::PERSIST, Some(interval))
+    pub fn new(interval: impl Into<EventTimeout>) -> Event<Inactive<Interval>> {
+        Inactive::new(Interval, None, EventFlags::PERSIST, Some(interval.into()))
+    }
+}
+
+impl<Src: AsRawFd> Io<Src> {
+    /// Creates an fd event which takes ownership of `source`, watching its
+    /// file descriptor for the given `flags`.
+    pub fn new(source: Src, flags: EventFlags, timeout: Option<Duration>) -> Event<Inactive<Self>> {
+        let fd = source.as_raw_fd();
+        Inactive::new(
+            Io(source, PhantomData),
+            Some(fd),
+            flags,
+            timeout.map(EventTimeout::Duration),
+        )
     }
 }
 
 impl Oneshot {
-    pub fn new(timeout: Duration) -> Event<Inactive<Oneshot>> {
-        Inactive::new(None, EventFlags::empty(), Some(timeout))
+    pub fn new(timeout: impl Into<EventTimeout>) -> Event<Inactive<Oneshot>> {
+        Inactive::new(Oneshot, None, EventFlags::empty(), Some(timeout.into()))
+    }
+}
+
+/// The timeout an event is spawned with, which is either a plain `Duration`,
+/// or a `CommonTimeout` from the `Base` the event is spawned on.
+#[derive(Clone, Debug)]
+pub enum EventTimeout {
+    Duration(Duration),
+    Common(CommonTimeout),
+}
+
+impl EventTimeout {
+    /// The duration of the timeout.
+    pub fn duration(&self) -> Duration {
+        match self {
+            EventTimeout::Duration(duration) => *duration,
+            EventTimeout::Common(common) => common.duration(),
+        }
+    }
+
+    /// The `timeval` handed to `event_add`, which for a common timeout is the
+    /// encoded value libevent returned for it.
+    pub(crate) fn to_timeval(&self) -> libevent_sys::timeval {
+        match self {
+            EventTimeout::Duration(duration) => to_timeval(*duration),
+            EventTimeout::Common(common) => common.timeval(),
+        }
+    }
+}
+
+impl From<Duration> for EventTimeout {
+    fn from(duration: Duration) -> Self {
+        EventTimeout::Duration(duration)
+    }
+}
+
+impl From<CommonTimeout> for EventTimeout {
+    fn from(common: CommonTimeout) -> Self {
+        EventTimeout::Common(common)
+    }
+}
+
+impl From<&CommonTimeout> for EventTimeout {
+    fn from(common: &CommonTimeout) -> Self {
+        EventTimeout::Common(common.clone())
+    }
+}
+
+/// What to do with an event once its callback returns, which closures may
+/// return instead of `()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Leaves the event as it is, which is what returning `()` does.
+    Continue,
+    /// Stops the event, as if the closure called `stop` on its handle.
+    Stop,
+    /// Reschedules the event to fire after the given duration, as if the
+    /// closure called `reschedule` on its handle.
+    RescheduleIn(Duration),
+}
+
+impl From<()> for Control {
+    fn from(_: ()) -> Self {
+        Control::Continue
+    }
+}
+
+/// Why an fd event fired, as handed to the closures of `Fd` and `Io` events
+/// switched over via `with_readiness`.
+///
+/// Unlike `EventFlags`, it only covers the conditions libevent reports, and
+/// never flags which only make sense when creating an event (e.g., `PERSIST`
+/// or `ET`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Readiness(EventFlags);
+
+/// The forms in which fd closures can be handed the conditions their event
+/// fired on, i.e. `EventFlags` or `Readiness`.
+trait FiredOn {
+    fn from_flags(flags: EventFlags) -> Self;
+}
+
+impl FiredOn for EventFlags {
+    fn from_flags(flags: EventFlags) -> Self {
+        flags
+    }
+}
+
+impl FiredOn for Readiness {
+    fn from_flags(flags: EventFlags) -> Self {
+        let reported =
+            EventFlags::TIMEOUT | EventFlags::READ | EventFlags::WRITE | EventFlags::CLOSED;
+        Readiness(flags & reported)
+    }
+}
+
+impl Readiness {
+    /// The fd is readable.
+    pub fn readable(&self) -> bool {
+        self.0.contains(EventFlags::READ)
+    }
+
+    /// The fd is writable.
+    pub fn writable(&self) -> bool {
+        self.0.contains(EventFlags::WRITE)
+    }
+
+    /// The timeout of the event elapsed.
+    pub fn timed_out(&self) -> bool {
+        self.0.contains(EventFlags::TIMEOUT)
+    }
+
+    /// The peer closed the connection, which is only detected for events
+    /// created with `CLOSED`.
+    pub fn closed(&self) -> bool {
+        self.0.contains(EventFlags::CLOSED)
+    }
+}
+
+impl Signal {
+    pub fn new(signal: c_int) -> Event<Inactive<Signal>> {
+        Inactive::new(
+            Signal,
+            Some(signal),
+            EventFlags::SIGNAL | EventFlags::PERSIST,
+            None,
+        )
+    }
+}
+
+impl Trigger {
+    pub fn new() -> Event<Inactive<Trigger>> {
+        Inactive::new(Trigger, None, EventFlags::empty(), None)
     }
 }
 
@@ -42,24 +225,133 @@ impl Oneshot {
 pub(crate) struct EventInner<T> {
     inner: NonNull<libevent_sys::event>,
     finalizer: libevent_sys::event_finalize_callback_fn,
+    base: Arc<BaseState>,
+    signal_claim: Option<SignalClaim>,
+    timeout: Option<EventTimeout>,
+    finished: bool,
+    in_callback: Arc<AtomicBool>,
     _phantom: PhantomData<T>,
 }
 
 impl<T> EventInner<T> {
-    /// Creates a new `EventInner` given a raw `event`, and a "finalizer"
-    /// function which helps in `Drop` teardown.
+    /// Creates a new `EventInner` given a raw `event`, a "finalizer"
+    /// function which helps in `Drop` teardown, and the state of the `Base`
+    /// the event belongs to.
     // TODO: unsafe?
     pub(crate) fn from_raw(
         inner: NonNull<libevent_sys::event>,
         finalizer: libevent_sys::event_finalize_callback_fn,
+        base: Arc<BaseState>,
     ) -> Self {
         EventInner {
             inner,
             finalizer,
+            base,
+            signal_claim: None,
+            timeout: None,
+            finished: false,
+            in_callback: Arc::new(AtomicBool::new(false)),
             _phantom: Default::default(),
         }
     }
 
+    /// Attaches the claim on signal handling held by a signal event, which is
+    /// released once the event is dropped.
+    pub(crate) fn with_signal_claim(mut self, signal_claim: Option<SignalClaim>) -> Self {
+        self.signal_claim = signal_claim;
+        self
+    }
+
+    /// Records the timeout the event was spawned with, which `add_original`
+    /// restores.
+    pub(crate) fn with_timeout(mut self, timeout: Option<EventTimeout>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Fails if the `Base` this event belongs to has been freed, in which case
+    /// the raw `event` must no longer be handed to libevent.
+    fn check_base_alive(&self) -> io::Result<()> {
+        if self.base.is_alive() {
+            Ok(())
+        } else {
+            Err(Self::base_freed())
+        }
+    }
+
+    /// Runs `f`, which hands the raw `event` to libevent, while keeping the
+    /// `Base` the event belongs to from being freed on another thread. Fails
+    /// as `check_base_alive` does.
+    fn while_base_alive<O>(&self, f: impl FnOnce() -> io::Result<O>) -> io::Result<O> {
+        self.base
+            .while_alive(f)
+            .unwrap_or_else(|| Err(Self::base_freed()))
+    }
+
+    fn base_freed() -> io::Error {
+        io::Error::new(io::ErrorKind::Other, "Event base has already been freed")
+    }
+
+    /// Fails if the event has finished, in which case the raw `event` has
+    /// already been freed.
+    fn check_not_finished(&self) -> io::Result<()> {
+        if self.finished {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Event has already finished",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether the event has finished, and its raw `event` and closure have
+    /// been freed.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Frees the raw `event` along with its closure, even though handles to
+    /// the event may remain.
+    ///
+    /// This goes through `event_free_finalize`, so that libevent only runs the
+    /// finalizer (dropping the closure) and frees the event once the callback
+    /// is guaranteed not to be running or to run again, even if another thread
+    /// is running the loop. Until the loop gets to it, or the base is freed,
+    /// the closure stays alive.
+    ///
+    /// While the event's own callback is running on the current thread, the
+    /// closure is still on the stack, so the event is instead handed over to be
+    /// finalized once the callback returns.
+    pub(crate) fn finish(&mut self) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+
+        let raw = unsafe { self.as_raw() };
+        let finalized = self.base.while_alive(|| {
+            let deferred =
+                self.in_callback.load(Ordering::Acquire) && defer_finalize(raw, self.finalizer);
+            if !deferred {
+                unsafe { libevent_sys::event_free_finalize(0, raw.as_ptr(), self.finalizer) };
+            }
+        });
+
+        if finalized.is_none() {
+            // Once the base is freed, no callback can run anymore, and
+            // `event_free` would reach into the base, so the (already removed)
+            // raw event is leaked instead.
+            self.drop_context();
+        }
+    }
+
+    /// Marks the event as finished without handing it to libevent, for events
+    /// which failed to spawn, and which are freed by the caller instead.
+    pub(crate) fn discard(&mut self) {
+        self.finished = true;
+    }
+
     /// Unsafe because other parts rely on `*mut event` being not-null.
     pub(crate) unsafe fn as_raw(&self) -> NonNull<libevent_sys::event> {
         self.inner
@@ -71,12 +363,156 @@ impl<T> EventInner<T> {
     /// automatically, either via the callback wrapper, or RAII. Also, it is
     /// not necessary to call `stop` when dropping the event handle; it will be
     /// handled internally by libevent.
-    pub fn stop(&mut self) -> io::Result<()> {
-        if (unsafe { libevent_sys::event_del(self.inner.as_ptr()) }) == 0 {
+    pub fn stop(&self) -> io::Result<()> {
+        self.check_not_finished()?;
+
+        self.while_base_alive(|| {
+            if (unsafe { libevent_sys::event_del(self.inner.as_ptr()) }) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::new(io::ErrorKind::Other, "Failed to stop event"))
+            }
+        })
+    }
+
+    /// Makes the event active via `event_active`, so that its callback runs
+    /// with the given `flags` during the current or next loop iteration.
+    pub fn activate(&self, flags: EventFlags) -> io::Result<()> {
+        self.check_not_finished()?;
+
+        self.while_base_alive(|| {
+            unsafe { libevent_sys::event_active(self.inner.as_ptr(), flags.bits() as c_int, 0) };
             Ok(())
-        } else {
-            Err(io::Error::new(io::ErrorKind::Other, "Failed to stop event"))
+        })
+    }
+
+    /// Checks via `event_pending` whether the event is pending on any of the
+    /// given `flags`.
+    ///
+    /// A finished event, or one whose base has been freed, is never pending.
+    pub fn is_pending(&self, flags: EventFlags) -> bool {
+        if self.finished {
+            return false;
+        }
+
+        let pending = self.base.while_alive(|| unsafe {
+            libevent_sys::event_pending(
+                self.inner.as_ptr(),
+                flags.bits() as c_short,
+                std::ptr::null_mut(),
+            )
+        });
+
+        pending.is_some_and(|pending| pending != 0)
+    }
+
+    /// The time left until the event's pending timeout expires, which
+    /// `event_pending` reports via its `timeval` out-parameter as an absolute
+    /// time on the base's clock.
+    pub fn time_remaining(&self) -> Option<Duration> {
+        if self.finished {
+            return None;
+        }
+
+        self.base
+            .while_alive(|| self.time_remaining_raw())
+            .flatten()
+    }
+
+    fn time_remaining_raw(&self) -> Option<Duration> {
+        let mut expiry = libevent_sys::timeval {
+            tv_sec: 0,
+            tv_usec: 0,
+        };
+        let pending = unsafe {
+            libevent_sys::event_pending(
+                self.inner.as_ptr(),
+                EventFlags::TIMEOUT.bits() as c_short,
+                &mut expiry,
+            )
+        };
+
+        if pending == 0 {
+            return None;
         }
+
+        let mut now = libevent_sys::timeval {
+            tv_sec: 0,
+            tv_usec: 0,
+        };
+        unsafe {
+            let base = libevent_sys::event_get_base(self.inner.as_ptr());
+            libevent_sys::event_base_gettimeofday_cached(base, &mut now);
+        }
+
+        Some(from_timeval(expiry).saturating_sub(from_timeval(now)))
+    }
+
+    /// (Re-)adds the event via `event_add`, replacing any pending timeout with
+    /// `timeout`.
+    pub fn add(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.add_timeval(timeout.map(to_timeval))
+    }
+
+    /// (Re-)adds the event via `event_add` with the timeout it was spawned
+    /// with.
+    pub fn add_original(&self) -> io::Result<()> {
+        self.add_timeval(self.timeout.as_ref().map(EventTimeout::to_timeval))
+    }
+
+    fn add_timeval(&self, tv: Option<libevent_sys::timeval>) -> io::Result<()> {
+        self.check_not_finished()?;
+
+        let tv_ptr = tv
+            .as_ref()
+            .map_or(std::ptr::null(), |tv| tv as *const libevent_sys::timeval);
+
+        self.while_base_alive(|| {
+            if (unsafe { libevent_sys::event_add(self.inner.as_ptr(), tv_ptr) }) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::new(io::ErrorKind::Other, "Failed to add event"))
+            }
+        })
+    }
+
+    /// The flags the event fires on, via `event_get_events`.
+    pub fn flags(&self) -> io::Result<EventFlags> {
+        self.check_not_finished()?;
+
+        // `FINALIZE` is set on all spawned events, so leave it out.
+        let events = unsafe { libevent_sys::event_get_events(self.inner.as_ptr()) };
+        Ok(EventFlags::from_bits_truncate(events as u32) - EventFlags::FINALIZE)
+    }
+
+    /// The priority of the event, via `event_get_priority`.
+    pub fn priority(&self) -> io::Result<u32> {
+        self.check_not_finished()?;
+
+        let priority = unsafe { libevent_sys::event_get_priority(self.inner.as_ptr()) };
+        Ok(priority.max(0) as u32)
+    }
+
+    /// Whether the event was spawned on `base`, via `event_get_base`.
+    pub fn belongs_to(&self, base: &Base) -> io::Result<bool> {
+        self.check_not_finished()?;
+
+        let raw_base = unsafe { libevent_sys::event_get_base(self.inner.as_ptr()) };
+        Ok(raw_base == unsafe { base.as_raw() }.as_ptr())
+    }
+
+    /// A non-owning `Base` for the base the event belongs to, which is only
+    /// handed out to the event's callback, wrapped in a `CallbackContext`.
+    pub(crate) fn base_view(&self) -> io::Result<Base> {
+        self.check_not_finished()?;
+        self.check_base_alive()?;
+
+        let raw_base = unsafe { libevent_sys::event_get_base(self.inner.as_ptr()) };
+
+        NonNull::new(raw_base)
+            // While the callback runs, so does the loop of the owning base.
+            .map(|raw_base| unsafe { Base::view(raw_base, self.base.clone()) })
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Event has no base"))
     }
 
     fn __drop_context(
@@ -86,6 +522,11 @@ impl<T> EventInner<T> {
         let ptr = event.as_ptr();
         let ctx = unsafe { libevent_sys::event_get_callback_arg(ptr) };
 
+        // Nothing to drop if the context was never assigned.
+        if ctx.is_null() {
+            return;
+        }
+
         unsafe { (finalizer.unwrap())(event.as_ptr(), ctx) };
     }
 
@@ -96,6 +537,120 @@ impl<T> EventInner<T> {
     }
 }
 
+impl<T> EventInner<T> {
+    /// The file descriptor the event watches, via `event_get_fd`.
+    ///
+    /// Unlike `Oneshot` events, fd events never finish while a handle to
+    /// them exists, so the raw `event` is always there to be read.
+    fn fd_raw(&self) -> RawFd {
+        unsafe { libevent_sys::event_get_fd(self.inner.as_ptr()) }
+    }
+
+    /// Changes the conditions the event fires on, by removing it via
+    /// `event_del`, re-assigning it via `event_assign` with the same fd,
+    /// callback and context, and adding it back via `event_add` if it was
+    /// pending before.
+    ///
+    /// The event keeps its priority and the timeout it was spawned with.
+    fn set_interest_raw(&self, flags: EventFlags) -> io::Result<()> {
+        if flags.intersects(EventFlags::SIGNAL | EventFlags::FINALIZE) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid flags for an fd event",
+            ));
+        }
+
+        self.check_not_finished()?;
+        let base = self.base_view()?;
+
+        let any = EventFlags::TIMEOUT | EventFlags::READ | EventFlags::WRITE | EventFlags::CLOSED;
+        let was_pending = self.is_pending(any);
+
+        let ret = self.while_base_alive(|| {
+            base.check_flags(flags)?;
+            Ok(self.reassign_raw(flags))
+        })?;
+
+        if ret != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to assign event",
+            ));
+        }
+
+        if was_pending {
+            self.add_original()?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the event, and assigns it again with `flags` in place of those
+    /// it had, returning what `event_assign` did.
+    fn reassign_raw(&self, flags: EventFlags) -> c_int {
+        let ev = self.inner.as_ptr();
+
+        unsafe {
+            let base = libevent_sys::event_get_base(ev);
+            let fd = libevent_sys::event_get_fd(ev);
+            let callback = libevent_sys::event_get_callback(ev);
+            let ctx = libevent_sys::event_get_callback_arg(ev);
+            let priority = libevent_sys::event_get_priority(ev);
+
+            libevent_sys::event_del(ev);
+
+            let flags = flags | EventFlags::FINALIZE;
+            let ret =
+                libevent_sys::event_assign(ev, base, fd, flags.bits() as c_short, callback, ctx);
+            if ret == 0 {
+                libevent_sys::event_priority_set(ev, priority);
+            }
+            ret
+        }
+    }
+}
+
+impl<A> EventInner<Fd<A>> {
+    /// The file descriptor the event watches.
+    pub fn fd(&self) -> RawFd {
+        self.fd_raw()
+    }
+
+    /// Changes the conditions the event fires on (see `set_interest_raw`).
+    pub fn set_interest(&self, flags: EventFlags) -> io::Result<()> {
+        self.set_interest_raw(flags)
+    }
+}
+
+impl<Src, A> EventInner<Io<Src, A>> {
+    /// The file descriptor of the source the event owns.
+    pub fn fd(&self) -> RawFd {
+        self.fd_raw()
+    }
+
+    /// Changes the conditions the event fires on (see `set_interest_raw`).
+    pub fn set_interest(&self, flags: EventFlags) -> io::Result<()> {
+        self.set_interest_raw(flags)
+    }
+}
+
+impl EventInner<Interval> {
+    /// The interval the event was spawned with.
+    pub fn interval(&self) -> Duration {
+        self.timeout
+            .as_ref()
+            .map_or(Duration::default(), EventTimeout::duration)
+    }
+}
+
+impl EventInner<Signal> {
+    /// The signal number the event fires on, which libevent stores in place
+    /// of the fd (see `event_get_signal`).
+    pub fn signal(&self) -> c_int {
+        unsafe { libevent_sys::event_get_fd(self.inner.as_ptr()) }
+    }
+}
+
 // Activation & Synchronization variants
 
 /// Inactive event, which defines an event-type `T`.
@@ -106,8 +661,9 @@ impl<T> EventInner<T> {
 pub struct Inactive<T> {
     fd: Option<RawFd>,
     flags: EventFlags,
-    timeout: Option<Duration>,
-    _phantom: PhantomData<T>,
+    timeout: Option<EventTimeout>,
+    priority: Option<u32>,
+    kind: Option<T>,
 }
 
 /// Callback-local synchronization type used by `Base::spawn`.
@@ -116,7 +672,7 @@ pub struct Inactive<T> {
 pub struct Internal<T>(pub(crate) EventInner<T>);
 
 /// Thread-local synchronization type used by `Base::spawn_local`.
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 #[doc(hidden)]
 pub struct Local<T>(pub(crate) Rc<RefCell<EventInner<T>>>);
 
@@ -125,6 +681,38 @@ pub struct Local<T>(pub(crate) Rc<RefCell<EventInner<T>>>);
 #[doc(hidden)]
 pub struct LocalWeak<T>(pub(crate) std::rc::Weak<RefCell<EventInner<T>>>);
 
+/// Thread-safe synchronization type used by `Base::spawn_shared`.
+#[derive(Debug)]
+#[doc(hidden)]
+pub struct Shared<T>(pub(crate) Arc<Mutex<EventInner<T>>>);
+
+/// Downgraded version of `Shared` which does not count toward ownership.
+#[derive(Debug)]
+#[doc(hidden)]
+pub struct SharedWeak<T>(pub(crate) std::sync::Weak<Mutex<EventInner<T>>>);
+
+// Cloning a handle never clones the event-type `T`, so unlike a derive these
+// do not require `T: Clone`.
+impl<T> Clone for Local<T> {
+    fn clone(&self) -> Self {
+        Local(self.0.clone())
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(self.0.clone())
+    }
+}
+
+// libevent serializes all access to an event through its base's lock (which
+// requires threading support), and the event-type `T` is only a marker to the
+// handles (anything it owns lives alongside the closure).
+unsafe impl<T> Send for Shared<T> {}
+unsafe impl<T> Sync for Shared<T> {}
+unsafe impl<T> Send for SharedWeak<T> {}
+unsafe impl<T> Sync for SharedWeak<T> {}
+
 /// The exposed event handle which wraps the raw `event` with a defined
 /// synchronization method and contains other necessary metadata.
 #[derive(Debug)]
@@ -147,6 +735,18 @@ impl<S> Event<S> {
         self.stopped.load(Ordering::Relaxed)
     }
 
+    /// Fails if the event has been stopped, and is thus about to be freed.
+    pub(crate) fn check_not_stopped(&self) -> io::Result<()> {
+        if self.stopped() {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Event has been stopped",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     #[inline]
     pub(crate) fn set_in_callback(&self, in_cb: bool) {
         self.in_callback.store(in_cb, Ordering::Relaxed);
@@ -160,22 +760,86 @@ impl<S> Event<S> {
 
 impl Event<Inactive<Fd>> {
     pub fn new(fd: RawFd, flags: EventFlags, timeout: Option<Duration>) -> Self {
-        Inactive::new(Some(fd), flags, timeout)
+        Inactive::new(
+            Fd(PhantomData),
+            Some(fd),
+            flags,
+            timeout.map(EventTimeout::Duration),
+        )
+    }
+
+    /// Makes the closure be handed a `Readiness` in place of the raw
+    /// `EventFlags`.
+    pub fn with_readiness(self) -> Event<Inactive<Fd<Readiness>>> {
+        self.map_kind(|_| Fd(PhantomData))
+    }
+}
+
+impl<A> Event<Inactive<Fd<A>>> {
+    /// Sets the timeout the event is spawned with, replacing the one given to
+    /// `new`. Accepts a `CommonTimeout` as well as a plain `Duration`.
+    pub fn timeout(mut self, timeout: impl Into<EventTimeout>) -> Self {
+        self.inner.timeout = Some(timeout.into());
+        self
+    }
+
+    /// The file descriptor the event watches.
+    pub fn fd(&self) -> RawFd {
+        self.inner.fd.unwrap_or(-1)
     }
 
     /// Shouldn't be allowing Fd's to clone, so this is internal-only.
     pub(crate) fn __clone(&self) -> Self {
-        Inactive::new(
+        let mut ev = Inactive::new(
+            Fd(PhantomData),
             self.inactive_fd(),
             self.inactive_flags(),
             self.inactive_timeout(),
-        )
+        );
+        ev.inner.priority = self.spawn_priority();
+        ev
+    }
+}
+
+impl<Src> Event<Inactive<Io<Src>>> {
+    /// Same as `with_readiness` for `Fd` events.
+    pub fn with_readiness(self) -> Event<Inactive<Io<Src, Readiness>>> {
+        self.map_kind(|Io(source, _)| Io(source, PhantomData))
+    }
+}
+
+impl<Src, A> Event<Inactive<Io<Src, A>>> {
+    /// Sets the timeout the event is spawned with, replacing the one given to
+    /// `new`. Accepts a `CommonTimeout` as well as a plain `Duration`.
+    pub fn timeout(mut self, timeout: impl Into<EventTimeout>) -> Self {
+        self.inner.timeout = Some(timeout.into());
+        self
+    }
+
+    /// The file descriptor of the source the event owns.
+    pub fn fd(&self) -> RawFd {
+        self.inner.fd.unwrap_or(-1)
+    }
+}
+
+impl Event<Inactive<Interval>> {
+    /// The interval the event fires at.
+    pub fn interval(&self) -> Duration {
+        self.inner
+            .timeout
+            .as_ref()
+            .map_or(Duration::default(), EventTimeout::duration)
     }
 }
 
-/// These would normally be part of the `Event` trait, but I want to see if I
-/// can develop a more ergonomic API around event types. (i.e., Event<Fd> would
-/// expose `pub fn fd()`, but not the timer types.
+impl Event<Inactive<Signal>> {
+    /// The signal number the event fires on.
+    pub fn signal(&self) -> c_int {
+        self.inner.fd.unwrap_or(-1)
+    }
+}
+
+/// The settings of an inactive event, as read by `Base` when spawning it.
 impl<T> Event<Inactive<T>> {
     pub(crate) fn inactive_fd(&self) -> Option<RawFd> {
         self.inner.fd.as_ref().copied()
@@ -183,19 +847,75 @@ impl<T> Event<Inactive<T>> {
     pub(crate) fn inactive_flags(&self) -> EventFlags {
         self.inner.flags
     }
-    pub(crate) fn inactive_timeout(&self) -> Option<Duration> {
-        self.inner.timeout
+    pub(crate) fn inactive_timeout(&self) -> Option<EventTimeout> {
+        self.inner.timeout.clone()
+    }
+    /// Moves the event-type value (and whatever it owns) out, to be kept with
+    /// the closure once spawned.
+    pub(crate) fn take_kind(&mut self) -> T {
+        self.inner.kind.take().expect("Missing event kind")
+    }
+    /// Swaps the event-type value for one of another event-type, keeping the
+    /// rest of the settings.
+    fn map_kind<U>(self, f: impl FnOnce(T) -> U) -> Event<Inactive<U>> {
+        let Event {
+            inner,
+            in_callback,
+            stopped,
+        } = self;
+
+        Event {
+            inner: Inactive {
+                fd: inner.fd,
+                flags: inner.flags,
+                timeout: inner.timeout,
+                priority: inner.priority,
+                kind: inner.kind.map(f),
+            },
+            in_callback,
+            stopped,
+        }
+    }
+}
+
+impl<T> Event<Inactive<T>> {
+    /// The flags the event is spawned with.
+    pub fn flags(&self) -> EventFlags {
+        self.inner.flags
+    }
+
+    /// Sets the priority the event is spawned with, where lower values are
+    /// serviced first. Must be less than the number of priorities given to
+    /// `Base::init_priorities`.
+    ///
+    /// Without this, the event gets libevent's default priority, which is
+    /// half the number of priorities of the base.
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.inner.priority = Some(priority);
+        self
+    }
+
+    /// The priority the event is spawned with, as set via `priority`, or
+    /// `None` for libevent's default.
+    pub fn spawn_priority(&self) -> Option<u32> {
+        self.inner.priority
     }
 }
 
 impl<T> Inactive<T> {
-    fn new(fd: Option<RawFd>, flags: EventFlags, timeout: Option<Duration>) -> Event<Self> {
+    fn new(
+        kind: T,
+        fd: Option<RawFd>,
+        flags: EventFlags,
+        timeout: Option<EventTimeout>,
+    ) -> Event<Self> {
         Event {
             inner: Inactive {
                 fd,
                 flags,
                 timeout,
-                _phantom: Default::default(),
+                priority: None,
+                kind: Some(kind),
             },
             in_callback: Arc::new(AtomicBool::new(false)),
             stopped: Arc::new(AtomicBool::new(false)),
@@ -228,6 +948,14 @@ impl<T> Downgrade for Local<T> {
     }
 }
 
+impl<T> Downgrade for Shared<T> {
+    type Weak = SharedWeak<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        SharedWeak(Arc::downgrade(&self.0))
+    }
+}
+
 impl<S: Downgrade> Downgrade for Event<S> {
     type Weak = Event<S::Weak>;
 
@@ -240,11 +968,146 @@ impl<S: Downgrade> Downgrade for Event<S> {
     }
 }
 
+/// Results of operations on the closure's (weak) handle, for when all other
+/// handles have been dropped and the event is thus finished.
+pub(crate) trait WhenFinished {
+    fn when_finished() -> Self;
+}
+
+impl<T> WhenFinished for io::Result<T> {
+    fn when_finished() -> Self {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Event has already finished",
+        ))
+    }
+}
+
+impl<T> WhenFinished for Option<T> {
+    fn when_finished() -> Self {
+        None
+    }
+}
+
+impl WhenFinished for bool {
+    fn when_finished() -> Self {
+        false
+    }
+}
+
+/// For fds and signal numbers, where libevent uses -1 for "none".
+impl WhenFinished for c_int {
+    fn when_finished() -> Self {
+        -1
+    }
+}
+
+impl WhenFinished for Duration {
+    fn when_finished() -> Self {
+        Duration::default()
+    }
+}
+
+/// Abstraction over the handles a callback holds to its own event, so that
+/// `handle_wrapped_callback` can act on the event without knowing the handle
+/// type.
+pub(crate) trait CallbackHandle {
+    /// Acts on the `Control` value returned by the closure.
+    fn apply(&mut self, control: Control) -> io::Result<()>;
+
+    /// Frees the event once it has finished (i.e., a `Oneshot` which has
+    /// fired).
+    fn finish(self);
+
+    /// Marks the event as finished for an event which failed to spawn, so that
+    /// neither this nor any other handle hands it to libevent once dropped.
+    fn discard(self);
+
+    /// Whether no handle but the closure's own is left, so that a stopped
+    /// event can never be added back, and is freed right away.
+    fn is_sole_handle(&self) -> bool;
+}
+
+/// Implements `CallbackHandle::apply` through the `stop` and `reschedule`
+/// which `handle_methods` generates for each handle.
+macro_rules! apply_control {
+    () => {
+        fn apply(&mut self, control: Control) -> io::Result<()> {
+            match control {
+                Control::Continue => Ok(()),
+                Control::Stop => self.stop(),
+                Control::RescheduleIn(timeout) => self.reschedule(timeout),
+            }
+        }
+    };
+}
+
+impl<T> CallbackHandle for Event<Internal<T>> {
+    apply_control!();
+
+    fn finish(self) {
+        // Nothing but the closure could have freed the event until now.
+        let raw = unsafe { self.inner.0.as_raw() };
+        self.inner.0.base.untrack_spawned(raw);
+        drop(self)
+    }
+
+    fn discard(mut self) {
+        self.inner.0.discard();
+    }
+
+    fn is_sole_handle(&self) -> bool {
+        true
+    }
+}
+
+impl<T> CallbackHandle for Event<LocalWeak<T>> {
+    apply_control!();
+
+    fn finish(self) {
+        if let Some(inner) = self.inner.0.upgrade() {
+            inner.borrow_mut().finish();
+        }
+    }
+
+    fn discard(self) {
+        if let Some(inner) = self.inner.0.upgrade() {
+            inner.borrow_mut().discard();
+        }
+    }
+
+    fn is_sole_handle(&self) -> bool {
+        self.inner.0.strong_count() == 0
+    }
+}
+
+impl<T> CallbackHandle for Event<SharedWeak<T>> {
+    apply_control!();
+
+    fn finish(self) {
+        if let Some(inner) = self.inner.0.upgrade() {
+            inner.lock().unwrap_or_else(|e| e.into_inner()).finish();
+        }
+    }
+
+    fn discard(self) {
+        if let Some(inner) = self.inner.0.upgrade() {
+            inner.lock().unwrap_or_else(|e| e.into_inner()).discard();
+        }
+    }
+
+    fn is_sole_handle(&self) -> bool {
+        self.inner.0.strong_count() == 0
+    }
+}
+
 impl<T> From<EventInner<T>> for Event<Internal<T>> {
     fn from(inner: EventInner<T>) -> Self {
+        let in_callback = inner.in_callback.clone();
+
         Event {
             inner: Internal(inner),
-            in_callback: Arc::new(AtomicBool::new(false)),
+            in_callback,
             stopped: Arc::new(AtomicBool::new(false)),
         }
     }
@@ -252,67 +1115,293 @@ impl<T> From<EventInner<T>> for Event<Internal<T>> {
 
 impl<T> From<EventInner<T>> for Event<Local<T>> {
     fn from(inner: EventInner<T>) -> Self {
+        let in_callback = inner.in_callback.clone();
+
         Event {
             inner: Local(Rc::new(RefCell::new(inner))),
-            in_callback: Arc::new(AtomicBool::new(false)),
+            in_callback,
             stopped: Arc::new(AtomicBool::new(false)),
         }
     }
 }
 
-impl<T> Event<Internal<T>> {
-    fn with_inner<F, O>(&mut self, f: F) -> O
-    where
-        F: Fn(&mut EventInner<T>) -> O,
-    {
-        let ev = &mut self.inner.0;
-        f(ev)
+impl<T> From<EventInner<T>> for Event<Shared<T>> {
+    fn from(inner: EventInner<T>) -> Self {
+        let in_callback = inner.in_callback.clone();
+
+        Event {
+            inner: Shared(Arc::new(Mutex::new(inner))),
+            in_callback,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
     }
+}
 
-    pub fn stop(&mut self) -> io::Result<()> {
-        self.set_stopped(true);
-        self.with_inner(|inner| inner.stop())
+/// Access to the `EventInner` behind each synchronization type, through which
+/// the methods shared by all event handles (see `handle_methods`) reach it.
+pub(crate) trait HandleInner<T> {
+    /// Runs `f` on the event, unless the handle is a weak one whose event is
+    /// gone, in which case `O::when_finished()` is returned instead.
+    fn with_inner<O: WhenFinished>(&self, f: impl FnOnce(&EventInner<T>) -> O) -> O;
+
+    /// Whether the event has finished (see `Event::is_finished`).
+    fn is_finished(&self) -> bool {
+        self.with_inner(|inner| inner.is_finished())
     }
 }
 
-impl<T> Event<Local<T>> {
-    fn with_inner<F, O>(&self, f: F) -> O
-    where
-        F: Fn(&mut EventInner<T>) -> O,
-    {
-        let mut ev = self.inner.0.borrow_mut();
-        f(&mut *ev)
+impl<T> HandleInner<T> for Internal<T> {
+    fn with_inner<O: WhenFinished>(&self, f: impl FnOnce(&EventInner<T>) -> O) -> O {
+        f(&self.0)
+    }
+}
+
+impl<T> HandleInner<T> for Local<T> {
+    fn with_inner<O: WhenFinished>(&self, f: impl FnOnce(&EventInner<T>) -> O) -> O {
+        f(&self.0.borrow())
+    }
+}
+
+impl<T> HandleInner<T> for LocalWeak<T> {
+    fn with_inner<O: WhenFinished>(&self, f: impl FnOnce(&EventInner<T>) -> O) -> O {
+        match self.0.upgrade() {
+            Some(upgraded) => f(&upgraded.borrow()),
+            None => O::when_finished(),
+        }
+    }
+
+    /// For the closure's handle, this also covers all other handles having
+    /// been dropped.
+    fn is_finished(&self) -> bool {
+        self.0
+            .upgrade()
+            .map_or(true, |inner| inner.borrow().is_finished())
     }
+}
 
-    pub fn stop(&mut self) -> io::Result<()> {
-        self.set_stopped(true);
-        self.with_inner(|inner| inner.stop())
+impl<T> HandleInner<T> for Shared<T> {
+    fn with_inner<O: WhenFinished>(&self, f: impl FnOnce(&EventInner<T>) -> O) -> O {
+        f(&self.0.lock().unwrap_or_else(|e| e.into_inner()))
     }
 }
 
-impl<T> Event<LocalWeak<T>> {
-    fn with_inner<F, O>(&self, f: F) -> O
+impl<T> HandleInner<T> for SharedWeak<T> {
+    fn with_inner<O: WhenFinished>(&self, f: impl FnOnce(&EventInner<T>) -> O) -> O {
+        match self.0.upgrade() {
+            Some(upgraded) => f(&upgraded.lock().unwrap_or_else(|e| e.into_inner())),
+            None => O::when_finished(),
+        }
+    }
+
+    /// For the closure's handle, this also covers all other handles having
+    /// been dropped.
+    fn is_finished(&self) -> bool {
+        self.0.upgrade().map_or(true, |inner| {
+            inner
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .is_finished()
+        })
+    }
+}
+
+impl<S> Event<S> {
+    fn with_inner<T, O: WhenFinished>(&self, f: impl FnOnce(&EventInner<T>) -> O) -> O
     where
-        F: Fn(&mut EventInner<T>) -> O,
+        S: HandleInner<T>,
     {
-        let upgraded = self.inner.0.upgrade().unwrap();
-        let mut ev = upgraded.borrow_mut();
-        f(&mut *ev)
+        self.inner.with_inner(f)
+    }
+}
+
+/// Generates the methods shared by all event handles, which only differ in
+/// how they reach the `EventInner` (see `HandleInner`).
+macro_rules! handle_methods {
+    ($($handle:ident),*) => {$(
+        impl<T> Event<$handle<T>> {
+            /// The flags the event fires on, via `event_get_events`.
+            pub fn flags(&self) -> io::Result<EventFlags> {
+                self.with_inner(|inner| inner.flags())
+            }
+
+            /// The priority of the event, via `event_get_priority`.
+            pub fn priority(&self) -> io::Result<u32> {
+                self.with_inner(|inner| inner.priority())
+            }
+
+            /// Whether the event was spawned on `base`, via `event_get_base`.
+            pub fn belongs_to(&self, base: &Base) -> io::Result<bool> {
+                self.with_inner(|inner| inner.belongs_to(base))
+            }
+
+            /// Removes the event from its base via `event_del`, after which it
+            /// only fires again once added back via `restart` or `reschedule`.
+            pub fn stop(&mut self) -> io::Result<()> {
+                self.set_stopped(true);
+                self.with_inner(|inner| inner.stop())
+            }
+
+            /// Forces the event's callback to run with the given `flags`,
+            /// regardless of whether its conditions were met.
+            pub fn activate(&mut self, flags: EventFlags) -> io::Result<()> {
+                self.check_not_stopped()?;
+                self.with_inner(|inner| inner.activate(flags))
+            }
+
+            /// Whether the event is pending on any of the given `flags`, via
+            /// `event_pending`.
+            pub fn is_pending(&self, flags: EventFlags) -> bool {
+                self.with_inner(|inner| inner.is_pending(flags))
+            }
+
+            /// The time left until the event's timeout expires, or `None` if
+            /// no timeout is pending.
+            pub fn time_remaining(&self) -> Option<Duration> {
+                self.with_inner(|inner| inner.time_remaining())
+            }
+
+            /// Reschedules the event via `event_add`, so that its timeout
+            /// expires `timeout` from now. A stopped event is added back as
+            /// well.
+            pub fn reschedule(&mut self, timeout: Duration) -> io::Result<()> {
+                self.with_inner(|inner| inner.add(Some(timeout)))?;
+                self.set_stopped(false);
+                Ok(())
+            }
+
+            /// Adds a stopped event back to its base, with the timeout it was
+            /// spawned with.
+            pub fn restart(&mut self) -> io::Result<()> {
+                self.with_inner(|inner| inner.add_original())?;
+                self.set_stopped(false);
+                Ok(())
+            }
+
+            /// Removes the event from its base via `event_del`. Unlike `stop`,
+            /// the event keeps its closure, and can be added back via
+            /// `resume`.
+            pub fn pause(&mut self) -> io::Result<()> {
+                self.check_not_stopped()?;
+                self.with_inner(|inner| inner.stop())
+            }
+
+            /// Adds a paused event back to its base, with the timeout it was
+            /// spawned with.
+            pub fn resume(&mut self) -> io::Result<()> {
+                self.check_not_stopped()?;
+                self.with_inner(|inner| inner.add_original())
+            }
+
+            /// Adds a paused event back to its base, with the given `timeout`
+            /// in place of the one it was spawned with.
+            pub fn resume_with_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+                self.check_not_stopped()?;
+                self.with_inner(|inner| inner.add(Some(timeout)))
+            }
+
+            /// Whether the event has finished (i.e., a `Oneshot` which has
+            /// fired), in which case its raw `event` and closure have been
+            /// freed, and all other operations fail.
+            pub fn is_finished(&self) -> bool {
+                self.inner.is_finished()
+            }
+        }
+
+        impl<A> Event<$handle<Fd<A>>> {
+            /// The file descriptor the event watches, via `event_get_fd`.
+            pub fn fd(&self) -> RawFd {
+                self.with_inner(|inner| inner.fd())
+            }
+
+            /// Changes the conditions the event fires on (e.g., toggling
+            /// `WRITE`), keeping its closure and handles. Fails for flags which
+            /// make no sense for fd events, such as `SIGNAL`.
+            ///
+            /// A pending event is added back with the timeout it was spawned
+            /// with, while a paused one stays paused.
+            pub fn set_interest(&mut self, flags: EventFlags) -> io::Result<()> {
+                self.check_not_stopped()?;
+                self.with_inner(|inner| inner.set_interest(flags))
+            }
+        }
+
+        impl<Src, A> Event<$handle<Io<Src, A>>> {
+            /// The file descriptor of the source the event owns.
+            pub fn fd(&self) -> RawFd {
+                self.with_inner(|inner| inner.fd())
+            }
+
+            /// Same as `set_interest` for `Fd` events, keeping the source as
+            /// well.
+            pub fn set_interest(&mut self, flags: EventFlags) -> io::Result<()> {
+                self.check_not_stopped()?;
+                self.with_inner(|inner| inner.set_interest(flags))
+            }
+        }
+
+        impl Event<$handle<Interval>> {
+            /// The interval the event was spawned with.
+            pub fn interval(&self) -> Duration {
+                self.with_inner(|inner| inner.interval())
+            }
+        }
+
+        impl Event<$handle<Signal>> {
+            /// The signal number the event fires on.
+            pub fn signal(&self) -> c_int {
+                self.with_inner(|inner| inner.signal())
+            }
+        }
+    )*};
+}
+
+handle_methods!(Internal, Local, LocalWeak, Shared, SharedWeak);
+
+/// Marks the handles closures are handed to their own event, through which
+/// alone the `Base` running the callback may be reached.
+pub(crate) trait CallbackSide {
+    type Kind;
+}
+
+impl<T> CallbackSide for Internal<T> {
+    type Kind = T;
+}
+
+impl<T> CallbackSide for LocalWeak<T> {
+    type Kind = T;
+}
+
+impl<T> CallbackSide for SharedWeak<T> {
+    type Kind = T;
+}
+
+/// Reaches the `Base` running the callback through a `CallbackSide` handle,
+/// which is what `Event::context` is available for.
+#[doc(hidden)]
+pub trait CallbackBase {
+    fn base_view(&self) -> io::Result<Base>;
+}
+
+impl<S> CallbackBase for S
+where
+    S: CallbackSide + HandleInner<S::Kind>,
+{
+    fn base_view(&self) -> io::Result<Base> {
+        self.with_inner(|inner| inner.base_view())
     }
+}
 
-    pub fn stop(&mut self) -> io::Result<()> {
-        self.set_stopped(true);
-        self.with_inner(|inner| inner.stop())
+impl<S: CallbackBase> Event<S> {
+    /// The context through which the callback can reach the `Base` running
+    /// it, e.g. to spawn new events.
+    pub fn context(&self) -> io::Result<CallbackContext<'_>> {
+        self.inner.base_view().map(CallbackContext::new)
     }
 }
 
 impl<T> Drop for EventInner<T> {
     fn drop(&mut self) {
-        self.drop_context();
-
-        let raw = unsafe { self.as_raw() };
-
-        unsafe { libevent_sys::event_free(raw.as_ptr()) };
+        self.finish();
     }
 }
 
@@ -322,24 +1411,139 @@ impl<T> Drop for EventInner<T> {
 /// For example, event-type `Interval` does not have an associated file
 /// descriptor, so it is better just to mask the implicitly-invalid fd value
 /// libevent passes into the callback.
+///
+/// Closures of repeating event-types may return either `()` or a `Control`
+/// value, which the callback then acts on.
+///
+/// `Fd` and `Io` closures are handed the raw `EventFlags`, or a `Readiness`
+/// for events switched over via `with_readiness`.
+///
+/// The closure is taken out of `cb` by event-types which only run it once,
+/// after which the event is finished and freed.
+///
+/// `self` is the event-type value the event was created with, which lives
+/// alongside the closure for as long as the event does.
 pub trait Exec<S, F> {
-    fn exec(ev: &mut Event<S>, fd: RawFd, flags: EventFlags, cb: &mut F);
+    fn exec(
+        &mut self,
+        ev: &mut Event<S>,
+        fd: RawFd,
+        flags: EventFlags,
+        cb: &mut Option<F>,
+    ) -> Control;
 }
 
-impl<S, F: FnMut(&mut Event<S>, RawFd, EventFlags)> Exec<S, F> for Fd {
-    fn exec(ev: &mut Event<S>, fd: RawFd, flags: EventFlags, cb: &mut F) {
-        cb(ev, fd, flags)
+impl<S, A, F, R> Exec<S, F> for Fd<A>
+where
+    A: FiredOn,
+    F: FnMut(&mut Event<S>, RawFd, A) -> R,
+    R: Into<Control>,
+{
+    fn exec(
+        &mut self,
+        ev: &mut Event<S>,
+        fd: RawFd,
+        flags: EventFlags,
+        cb: &mut Option<F>,
+    ) -> Control {
+        match cb {
+            Some(cb) => cb(ev, fd, A::from_flags(flags)).into(),
+            None => Control::Continue,
+        }
     }
 }
 
-impl<S, F: FnMut(&mut Event<S>)> Exec<S, F> for Interval {
-    fn exec(ev: &mut Event<S>, _fd: RawFd, _flags: EventFlags, cb: &mut F) {
-        cb(ev)
+impl<S, Src, A, F, R> Exec<S, F> for Io<Src, A>
+where
+    A: FiredOn,
+    F: FnMut(&mut Event<S>, &mut Src, A) -> R,
+    R: Into<Control>,
+{
+    fn exec(
+        &mut self,
+        ev: &mut Event<S>,
+        _fd: RawFd,
+        flags: EventFlags,
+        cb: &mut Option<F>,
+    ) -> Control {
+        match cb {
+            Some(cb) => cb(ev, &mut self.0, A::from_flags(flags)).into(),
+            None => Control::Continue,
+        }
     }
 }
 
-impl<S, F: FnMut(&mut Event<S>)> Exec<S, F> for Oneshot {
-    fn exec(ev: &mut Event<S>, _fd: RawFd, _flags: EventFlags, cb: &mut F) {
-        cb(ev)
+impl<S, F, R> Exec<S, F> for Signal
+where
+    F: FnMut(&mut Event<S>, c_int) -> R,
+    R: Into<Control>,
+{
+    fn exec(
+        &mut self,
+        ev: &mut Event<S>,
+        fd: RawFd,
+        _flags: EventFlags,
+        cb: &mut Option<F>,
+    ) -> Control {
+        // libevent passes the signal number in place of the fd.
+        match cb {
+            Some(cb) => cb(ev, fd).into(),
+            None => Control::Continue,
+        }
+    }
+}
+
+impl<S, F, R> Exec<S, F> for Trigger
+where
+    F: FnMut(&mut Event<S>, EventFlags) -> R,
+    R: Into<Control>,
+{
+    fn exec(
+        &mut self,
+        ev: &mut Event<S>,
+        _fd: RawFd,
+        flags: EventFlags,
+        cb: &mut Option<F>,
+    ) -> Control {
+        match cb {
+            Some(cb) => cb(ev, flags).into(),
+            None => Control::Continue,
+        }
+    }
+}
+
+impl<S, F, R> Exec<S, F> for Interval
+where
+    F: FnMut(&mut Event<S>) -> R,
+    R: Into<Control>,
+{
+    fn exec(
+        &mut self,
+        ev: &mut Event<S>,
+        _fd: RawFd,
+        _flags: EventFlags,
+        cb: &mut Option<F>,
+    ) -> Control {
+        match cb {
+            Some(cb) => cb(ev).into(),
+            None => Control::Continue,
+        }
+    }
+}
+
+impl<S, F: FnOnce(&mut Event<S>)> Exec<S, F> for Oneshot {
+    fn exec(
+        &mut self,
+        ev: &mut Event<S>,
+        _fd: RawFd,
+        _flags: EventFlags,
+        cb: &mut Option<F>,
+    ) -> Control {
+        if let Some(cb) = cb.take() {
+            cb(ev)
+        }
+
+        // The event is finished either way, now that the closure is gone.
+        Control::Continue
     }
 }
@@ -2,49 +2,78 @@
 //!
 //! [libevent]: https://libevent.org/
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 mod event;
-pub use event::{Event, Fd, Interval, Oneshot};
+pub use event::{
+    Control, Event, EventTimeout, Fd, Interval, Io, Oneshot, Readiness, Signal, Trigger,
+};
 
 mod base;
+use base::ExitTimer;
 pub use base::{
-    Base, EventCallbackCtx, EventCallbackFlags, EventFlags, EvutilSocket, ExitReason, LoopFlags,
+    supported_methods, Base, CallbackContext, CommonTimeout, EventCallbackCtx, EventCallbackFlags,
+    EventCountFlags, EventFlags, EvutilSocket, ExitReason, LoopFlags, LoopOutcome,
 };
 
+mod config;
+pub use config::{BackendFeatures, BaseConfigFlags, EventConfig};
+
+pub mod debug;
+
+#[cfg(feature = "threading")]
+pub mod thread;
+
 /// The context passed into `handle_wrapped_callback`, which handles event-type
 /// specific metadata for trampolining into the user-supplied closure.
 pub(crate) struct EventCallbackWrapper<S, T, F> {
-    inner: F,
+    inner: Option<F>,
     event: Option<Event<S>>,
-    _phantom: std::marker::PhantomData<T>,
+    base: std::sync::Arc<base::BaseState>,
+    kind: T,
 }
 
 impl Base {
-    /// Turns the libevent base once.
-    // TODO: any way to show if work was done?
-    pub fn turn(&self) -> ExitReason {
-        self.loop_(LoopFlags::NONBLOCK)
+    /// Turns the libevent base once, without blocking.
+    pub fn turn(&self) -> LoopOutcome {
+        self.loop_counted(LoopFlags::NONBLOCK)
     }
 
     /// Turns the libevent base until exit or timeout duration reached.
+    ///
+    /// The timeout only applies to this call; if the loop returns before it
+    /// elapses, later runs are unaffected by it.
     pub fn run_timeout(&self, timeout: Duration) -> ExitReason {
-        if self.loopexit(timeout) != 0 {
+        let _timer = match ExitTimer::new(self, timeout) {
+            Ok(timer) => timer,
             // TODO: This conflates errors, is it ok?
-            return ExitReason::Error;
+            Err(_) => return ExitReason::Error,
         };
         self.loop_(LoopFlags::empty())
     }
 
+    /// Turns the libevent base until exit or the `deadline` is reached.
+    ///
+    /// A `deadline` in the past still turns the base once.
+    pub fn run_until(&self, deadline: Instant) -> ExitReason {
+        self.run_timeout(deadline.saturating_duration_since(Instant::now()))
+    }
+
     /// Turns the libevent base until next active event.
-    pub fn run_until_event(&self, timeout: Option<Duration>) -> ExitReason {
-        if let Some(timeout) = timeout {
-            if self.loopexit(timeout) != 0 {
+    ///
+    /// As with `run_timeout`, the `timeout` only applies to this call.
+    pub fn run_until_event(&self, timeout: Option<Duration>) -> LoopOutcome {
+        let _timer = match timeout.map(|timeout| ExitTimer::new(self, timeout)) {
+            Some(Err(_)) => {
                 // TODO: This conflates errors, is it ok?
-                return ExitReason::Error;
+                return LoopOutcome {
+                    reason: ExitReason::Error,
+                    dispatched: 0,
+                };
             }
-        }
-        self.loop_(LoopFlags::ONCE)
+            timer => timer,
+        };
+        self.loop_counted(LoopFlags::ONCE)
     }
 
     /// Turns the libevent base until exit.
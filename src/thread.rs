@@ -0,0 +1,162 @@
+//! Initialization of libevent's thread support, which makes bases lock
+//! themselves so that events can be used from other threads than the one
+//! running the loop.
+//!
+//! With the `threading` feature, [Base::new] initializes thread support
+//! automatically, so calling [init] explicitly is only necessary to find out
+//! whether that succeeded, or before creating bases some other way.
+//!
+//! [Base::new]: ../struct.Base.html#method.new
+//! [init]: fn.init.html
+
+use std::io;
+use std::os::raw::{c_int, c_ulong};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::base::any_base_created;
+use crate::Base;
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+static INIT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Initializes libevent's thread support via `evthread_use_pthreads`.
+///
+/// Calling this more than once is harmless, but the first call must happen
+/// before any `Base` is created, as earlier bases would not lock themselves.
+pub fn init() -> io::Result<()> {
+    let _guard = INIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    init_once(&INITIALIZED, any_base_created, || unsafe {
+        libevent_sys::evthread_use_pthreads()
+    })
+}
+
+/// The steps of `init`, apart from the process-wide state they act on.
+fn init_once(
+    initialized: &AtomicBool,
+    base_created: impl FnOnce() -> bool,
+    use_pthreads: impl FnOnce() -> c_int,
+) -> io::Result<()> {
+    if initialized.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    if base_created() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Thread support must be initialized before any event base is created",
+        ));
+    }
+
+    if use_pthreads() != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Failed to initialize thread support",
+        ));
+    }
+
+    initialized.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Whether thread support has been initialized via `init`.
+pub fn is_initialized() -> bool {
+    INITIALIZED.load(Ordering::Acquire)
+}
+
+/// Wrapper for libevent's `evthread_make_base_notifiable`, which lets other
+/// threads wake up the loop of `base` when they change its events.
+///
+/// Bases created after `init` are already notifiable, so this is only needed
+/// for bases created some other way.
+pub fn make_base_notifiable(base: &Base) -> io::Result<()> {
+    let ret = unsafe { libevent_sys::evthread_make_base_notifiable(base.as_raw().as_ptr()) };
+
+    if ret != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Failed to make event base notifiable",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Wrapper for libevent's `evthread_enable_lock_debugging`, which makes
+/// libevent abort on common locking errors.
+///
+/// Must be called after `init`, but before any `Base` is created.
+pub fn enable_lock_debugging() -> io::Result<()> {
+    let _guard = INIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    if !INITIALIZED.load(Ordering::Acquire) {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Thread support has not been initialized",
+        ));
+    }
+
+    if any_base_created() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Lock debugging must be enabled before any event base is created",
+        ));
+    }
+
+    unsafe { libevent_sys::evthread_enable_lock_debugging() };
+    Ok(())
+}
+
+/// Wrapper for libevent's `evthread_set_id_callback`, which replaces the
+/// function libevent uses to identify the current thread, e.g. when lock
+/// debugging checks which thread holds a lock.
+///
+/// `init` already installs one based on `pthread_self`; `None` removes it.
+pub fn set_id_callback(id_fn: Option<extern "C" fn() -> c_ulong>) {
+    let id_fn = id_fn.map(|id_fn| id_fn as unsafe extern "C" fn() -> c_ulong);
+    unsafe { libevent_sys::evthread_set_id_callback(id_fn) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_is_idempotent() {
+        let _base = Base::new().unwrap();
+
+        // `Base::new` initialized thread support already.
+        assert!(is_initialized());
+        init().unwrap();
+        init().unwrap();
+        assert!(is_initialized());
+
+        let initialized = AtomicBool::new(false);
+        let mut calls = 0;
+        for _ in 0..2 {
+            init_once(
+                &initialized,
+                || false,
+                || {
+                    calls += 1;
+                    0
+                },
+            )
+            .unwrap();
+        }
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn init_fails_once_base_exists() {
+        let initialized = AtomicBool::new(false);
+        let err = init_once(&initialized, || true, || 0).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert!(!initialized.load(Ordering::Acquire));
+
+        // Nor does a failure of libevent count as initialized.
+        assert!(init_once(&initialized, || false, || -1).is_err());
+        assert!(!initialized.load(Ordering::Acquire));
+    }
+}